@@ -1,15 +1,116 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand};
+
+use crate::config::LandStrategy;
 
 #[derive(Parser, Debug)]
 #[command(
     name = "wb",
     about = "git-branch interface backed by git-worktree",
     version,
-    disable_help_subcommand = true
+    disable_help_subcommand = true,
+    args_conflicts_with_subcommands = true,
+    group(ArgGroup::new("mode").multiple(false))
 )]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Command>,
+
+    /// Delete branch(es) and their worktrees
+    #[arg(short = 'd', long = "delete", group = "mode")]
+    pub delete: bool,
+
+    /// Shortcut for --delete --force
+    #[arg(short = 'D', group = "mode")]
+    pub force_delete: bool,
+
+    /// Rename a branch and move its worktree
+    #[arg(short = 'm', long = "move", group = "mode")]
+    pub rename: bool,
+
+    /// Shortcut for --move --force
+    #[arg(short = 'M', group = "mode")]
+    pub force_rename: bool,
+
+    /// Copy a branch and create a new worktree
+    #[arg(short = 'c', long = "copy", group = "mode")]
+    pub copy: bool,
+
+    /// Shortcut for --copy --force
+    #[arg(short = 'C', group = "mode")]
+    pub force_copy: bool,
+
+    /// Print the name of the current branch
+    #[arg(long = "show-current", group = "mode")]
+    pub show_current: bool,
+
+    /// List branches, optionally matching the given patterns
+    #[arg(short = 'l', long = "list", group = "mode")]
+    pub list: bool,
+
+    /// Show sha and subject when listing; twice for upstream and worktree path
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count)]
+    pub verbose: u8,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    /// Branch names, or glob patterns with --list
+    pub args: Vec<String>,
+}
+
+/// Branch filters shared by `wb list` and `wb -l`.
+#[derive(Args, Debug, Default)]
+pub struct FilterArgs {
+    /// List remote-tracking branches
+    #[arg(short = 'r', long = "remotes", conflicts_with = "all")]
+    pub remotes: bool,
+
+    /// List both local and remote-tracking branches
+    #[arg(short = 'a', long = "all")]
+    pub all: bool,
+
+    /// Sort by the given for-each-ref key (e.g. -committerdate)
+    #[arg(long, value_name = "KEY")]
+    pub sort: Option<String>,
+
+    /// Only list branches merged into COMMIT (default: HEAD)
+    #[arg(long, value_name = "COMMIT", num_args = 0..=1, default_missing_value = "HEAD")]
+    pub merged: Option<String>,
+
+    /// Only list branches not merged into COMMIT (default: HEAD)
+    #[arg(long, value_name = "COMMIT", num_args = 0..=1, default_missing_value = "HEAD")]
+    pub no_merged: Option<String>,
+
+    /// Only list branches containing COMMIT (default: HEAD)
+    #[arg(long, value_name = "COMMIT", num_args = 0..=1, default_missing_value = "HEAD")]
+    pub contains: Option<String>,
+
+    /// Only list branches not containing COMMIT (default: HEAD)
+    #[arg(long, value_name = "COMMIT", num_args = 0..=1, default_missing_value = "HEAD")]
+    pub no_contains: Option<String>,
+
+    /// Only list branches whose changes are in BASE (default: wb.base), even if squash- or rebase-merged
+    #[arg(long, value_name = "BASE", num_args = 0..=1)]
+    pub landed: Option<Option<String>>,
+
+    /// Only list branches whose tip was committed by you (user.email)
+    #[arg(long)]
+    pub mine: bool,
+}
+
+impl FilterArgs {
+    /// Whether any filter was given (filters imply list mode).
+    pub fn is_set(&self) -> bool {
+        self.remotes
+            || self.all
+            || self.sort.is_some()
+            || self.merged.is_some()
+            || self.no_merged.is_some()
+            || self.contains.is_some()
+            || self.no_contains.is_some()
+            || self.landed.is_some()
+            || self.mine
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -22,10 +123,151 @@ pub enum Command {
         /// Directory to clone into
         #[arg(long = "directory", short = 'd')]
         directory: Option<String>,
+
+        /// Name of the shell wrapper function (default: wb)
+        #[arg(long, value_name = "NAME")]
+        cmd: Option<String>,
+
+        /// Bind a key (ctrl-<letter> or alt-<letter>) to a branch picker that cds into the selection
+        #[arg(long, value_name = "KEY")]
+        bind: Option<String>,
     },
 
     /// List local branches
-    List,
+    List {
+        /// Show sha and subject; twice for upstream and worktree path
+        #[arg(short = 'v', long = "verbose", action = ArgAction::Count)]
+        verbose: u8,
+
+        /// Print branches as a JSON array
+        #[arg(long, conflicts_with_all = ["porcelain", "verbose"])]
+        json: bool,
+
+        /// Print branches in a stable, tab-separated format for scripts
+        #[arg(long, conflicts_with = "verbose")]
+        porcelain: bool,
+
+        /// Format each branch with a template, e.g. '{current} {branch} {path}'
+        /// (placeholders: branch, path, sha, upstream, ahead, behind, subject, current, dirty)
+        #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["json", "porcelain", "verbose"])]
+        format: Option<String>,
+
+        /// Show stacked branches as a tree under their parents
+        #[arg(long, conflicts_with_all = ["json", "porcelain", "verbose", "format", "remotes", "all"])]
+        stack: bool,
+
+        /// List every worktree with its state, including detached and stray ones
+        #[arg(long, conflicts_with_all = ["porcelain", "verbose", "format", "stack", "patterns"])]
+        worktrees: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
+
+        /// Only list branches matching these glob patterns
+        patterns: Vec<String>,
+    },
+
+    /// Show changes, upstream state, stashes and in-progress operations for every worktree
+    Status,
+
+    /// Fetch all remotes and fast-forward every branch that is behind its upstream
+    Sync,
+
+    /// Delete branches merged into the base branch or whose upstream is gone
+    Clean {
+        /// Also remove worktrees with local changes
+        #[arg(long, short = 'f')]
+        force: bool,
+
+        /// Only show what would be deleted
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+
+        /// Do not ask for confirmation
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Skip fetching remotes first
+        #[arg(long)]
+        no_fetch: bool,
+    },
+
+    /// Merge a branch into its base, remove its worktree and branch, and cd to the base
+    Land {
+        /// Branch to land (defaults to current branch)
+        branch: Option<String>,
+
+        /// Base branch to land into (default: the stack parent, else wb.base)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// How to integrate the branch (default: wb.landStrategy, or ff)
+        #[arg(long, value_enum)]
+        strategy: Option<LandStrategy>,
+    },
+
+    /// Rebase every stacked branch onto its parent, in its own worktree
+    Restack,
+
+    /// Attach to (or create) a tmux/zellij session for a branch's worktree
+    Session {
+        /// Branch whose session to attach to (defaults to current branch)
+        branch: Option<String>,
+    },
+
+    /// Open a branch's worktree in $VISUAL or wb.editor, creating it if needed
+    Open {
+        /// Branch to open (defaults to current branch)
+        branch: Option<String>,
+    },
+
+    /// Write a .code-workspace file with every worktree as a root folder
+    Workspace,
+
+    /// Browse and manage branches and worktrees in a full-screen dashboard
+    Ui,
+
+    /// Go back to the previously visited worktree (also `wb -`)
+    Back,
+
+    /// List recently visited worktrees, or cd into one of them
+    Recent {
+        /// Position in the list to cd into (1 is the previous worktree)
+        index: Option<usize>,
+
+        /// Only print the list, even on a terminal
+        #[arg(long, short = 'l', conflicts_with = "index")]
+        list: bool,
+    },
+
+    /// Print a worktree's variables (branch, path, port range, DB name)
+    Env {
+        /// Branch whose worktree to describe (defaults to current branch)
+        branch: Option<String>,
+
+        /// Also rewrite the worktree's wb.envFile
+        #[arg(long)]
+        write: bool,
+    },
+
+    /// Run a command in every worktree, in parallel
+    Foreach {
+        /// Only run in worktrees whose branch matches this glob
+        #[arg(long, value_name = "GLOB")]
+        pattern: Option<String>,
+
+        /// Number of commands to run at once (default: number of CPUs)
+        #[arg(long, short = 'j', value_name = "N")]
+        jobs: Option<usize>,
+
+        /// Keep starting commands after one fails
+        #[arg(long, short = 'k')]
+        keep_going: bool,
+
+        /// Command to run; a single argument is run with `sh -c`
+        #[arg(required = true, last = true, value_name = "CMD")]
+        command: Vec<String>,
+    },
 
     /// Create a branch with a worktree and cd into it
     Create {
@@ -34,6 +276,11 @@ pub enum Command {
 
         /// Start point (branch or commit) to create from
         from: Option<String>,
+
+        /// Clone build artifacts (wb.warmDirs) from another worktree: the given
+        /// branch's, else the start point's or the current one
+        #[arg(long, value_name = "BRANCH", num_args = 0..=1, require_equals = true)]
+        warm: Option<Option<String>>,
     },
 
     /// Delete branch(es) and their worktrees
@@ -54,6 +301,10 @@ pub enum Command {
 
         /// Old branch name (defaults to current branch)
         old_name: Option<String>,
+
+        /// Rename even if the new branch name already exists (like git branch -M)
+        #[arg(long)]
+        force: bool,
     },
 
     /// Copy a branch and create a new worktree
@@ -63,5 +314,9 @@ pub enum Command {
 
         /// Source branch to copy from (defaults to current branch)
         from: Option<String>,
+
+        /// Copy even if the new branch name already exists (like git branch -C)
+        #[arg(long)]
+        force: bool,
     },
 }
@@ -0,0 +1,14 @@
+use anyhow::{bail, Result};
+
+use crate::history;
+use crate::shell;
+
+/// Return to the previously visited worktree.
+/// `wb back` or `wb -`
+pub fn run() -> Result<()> {
+    let Some(path) = history::recent().into_iter().next() else {
+        bail!("fatal: no previous worktree to go back to");
+    };
+    shell::cd(&path);
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::commands::delete;
+use crate::config::WbConfig;
+use crate::git::{self, BranchFilter};
+use crate::terminal;
+use crate::worktree;
+
+/// Why a branch is a cleanup candidate.
+enum Reason {
+    Merged,
+    /// Squash- or rebase-merged: not an ancestor, but the changes are in the base.
+    Landed,
+    UpstreamGone,
+}
+
+impl Reason {
+    fn describe(&self, base: &str) -> String {
+        match self {
+            Reason::Merged => format!("merged into {}", base),
+            Reason::Landed => format!("squash/rebase-merged into {}", base),
+            Reason::UpstreamGone => "upstream gone".to_string(),
+        }
+    }
+}
+
+struct Candidate {
+    name: String,
+    reason: Reason,
+    /// Why the branch will be left alone, if it will.
+    skip: Option<String>,
+}
+
+/// Delete branches (and their worktrees) that are merged into the base branch
+/// (including squash and rebase merges) or whose upstream is gone.
+/// `wb clean [--force] [--dry-run] [--yes] [--no-fetch]`
+pub fn run(force: bool, dry_run: bool, yes: bool, fetch: bool) -> Result<()> {
+    let config = WbConfig::load()?;
+    let base = config.base_branch()?;
+
+    if fetch {
+        eprintln!("Fetching all remotes...");
+        git::run(&["fetch", "--all", "--prune"])?;
+    }
+
+    let candidates = find_candidates(&base, force)?;
+    if candidates.is_empty() {
+        eprintln!("Nothing to clean.");
+        return Ok(());
+    }
+
+    eprintln!("Branches to clean:");
+    let width = candidates.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for c in &candidates {
+        let reason = c.reason.describe(&base);
+        match c.skip {
+            Some(ref why) => eprintln!(
+                "  {:<width$}  {} {}",
+                c.name,
+                reason,
+                format!("(skipped: {})", why).yellow(),
+                width = width
+            ),
+            None => eprintln!("  {:<width$}  {}", c.name.red(), reason, width = width),
+        }
+    }
+
+    let targets: Vec<&Candidate> = candidates.iter().filter(|c| c.skip.is_none()).collect();
+    if dry_run || targets.is_empty() {
+        return Ok(());
+    }
+
+    if !yes && !terminal::is_interactive() {
+        bail!("fatal: refusing to delete branches without confirmation; pass --yes");
+    }
+    if !yes && !terminal::confirm(&format!("Delete {} branch(es)?", targets.len()))? {
+        bail!("Aborted.");
+    }
+
+    // Candidates were vetted above (merged or gone, clean unless --force),
+    // so delete them the way `wb -D` would.
+    for c in targets {
+        delete::run(std::slice::from_ref(&c.name), true)?;
+    }
+
+    Ok(())
+}
+
+fn find_candidates(base: &str, force: bool) -> Result<Vec<Candidate>> {
+    let merged: Vec<String> = git::list_branches(BranchFilter {
+        merged: Some(base.to_string()),
+        ..Default::default()
+    })?
+    .into_iter()
+    .map(|b| b.name)
+    .collect();
+
+    let current = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| worktree::find_worktree_for_path(&cwd).ok().flatten())
+        .and_then(|wt| wt.branch);
+
+    // Never offer the base or the repository's default branch
+    let default = git::default_branch().ok();
+
+    let mut candidates = Vec::new();
+    for branch in git::list_branches(BranchFilter::default())? {
+        if branch.name == base || default.as_deref() == Some(branch.name.as_str()) {
+            continue;
+        }
+        let reason = if merged.contains(&branch.name) {
+            Reason::Merged
+        } else if branch.tracking.gone {
+            Reason::UpstreamGone
+        } else if git::is_landed(&branch.name, base) {
+            Reason::Landed
+        } else {
+            continue;
+        };
+
+        let skip = if current.as_deref() == Some(branch.name.as_str()) {
+            Some("current worktree".to_string())
+        } else if force {
+            None
+        } else {
+            dirty_reason(&branch.name)?
+        };
+
+        candidates.push(Candidate {
+            name: branch.name,
+            reason,
+            skip,
+        });
+    }
+
+    Ok(candidates)
+}
+
+/// Why the branch's worktree should not be removed without `--force`, if any.
+fn dirty_reason(branch: &str) -> Result<Option<String>> {
+    let Some(wt) = worktree::find_worktree_for_branch(branch)? else {
+        return Ok(None);
+    };
+    let Ok(status) = worktree::status(&wt.path) else {
+        return Ok(None);
+    };
+    if let Some(op) = status.operation {
+        return Ok(Some(format!("{} in progress", op.name())));
+    }
+    if !status.is_clean() {
+        return Ok(Some("worktree has local changes".to_string()));
+    }
+    Ok(None)
+}
@@ -1,27 +1,55 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 
 use crate::config::WbConfig;
+use crate::environment;
 use crate::git;
+use crate::hooks::{self, Hook, HookContext};
+use crate::populate;
 use crate::resolve;
+use crate::shell;
+use crate::terminal;
+use crate::workspace;
 use crate::worktree;
 
 /// Copy a branch and create a new worktree.
-/// `wb copy <new> [<from>]`
-pub fn run(new_name: &str, from: Option<&str>) -> Result<()> {
+/// `wb copy <new> [<from>]` or `wb -c [<from>] <new>`
+pub fn run(new_name: &str, from: Option<&str>, force: bool) -> Result<()> {
     let old_name = match from {
-        Some(name) => name.to_string(),
+        Some(name) => resolve::existing_branch(name)?,
         None => current_branch_from_cwd()?,
     };
+    let new_path = copy(&old_name, new_name, force)?;
+    shell::cd(&new_path);
+    Ok(())
+}
 
+/// Copy `old_name` to `new_name` with a worktree of its own, returning the worktree path.
+pub fn copy(old_name: &str, new_name: &str, force: bool) -> Result<PathBuf> {
     let config = WbConfig::load()?;
 
     // Copy the git branch ref
-    git::copy_branch(&old_name, new_name, false)?;
+    git::copy_branch(old_name, new_name, force)?;
+    // The copied branch config includes the port range; the copy needs its own
+    environment::release_port(new_name);
 
     // Create worktree for the new branch
     let new_path = resolve::branch_to_worktree_path(&config, new_name);
     worktree::add_worktree(&new_path, new_name, false, None)?;
 
+    // The branch and worktree exist by now, so a failure is only reported
+    if let Some(src) = populate::source_worktree(Some(old_name)) {
+        match populate::copy_ignored(&src, &new_path, &config.copy_files) {
+            Ok(0) => {}
+            Ok(copied) => eprintln!("Copied {} ignored file(s) from '{}'", copied, src.display()),
+            Err(e) => terminal::warn(&e),
+        }
+    }
+    if let Err(e) = environment::write_env_file(&config, new_name, &new_path) {
+        terminal::warn(&e);
+    }
+
     eprintln!(
         "Branch '{}' copied to '{}', worktree at '{}'",
         old_name,
@@ -29,9 +57,17 @@ pub fn run(new_name: &str, from: Option<&str>) -> Result<()> {
         new_path.display()
     );
 
-    println!("__wb_cd:{}", new_path.display());
+    let old_wt = worktree::find_worktree_for_branch(old_name)?;
+    let ctx = HookContext {
+        branch: new_name,
+        path: Some(&new_path),
+        old_branch: Some(old_name),
+        old_path: old_wt.as_ref().map(|wt| wt.path.as_path()),
+    };
+    hooks::run_post(Hook::PostCopy, &ctx);
+    workspace::refresh();
 
-    Ok(())
+    Ok(new_path)
 }
 
 fn current_branch_from_cwd() -> Result<String> {
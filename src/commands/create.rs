@@ -1,28 +1,73 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{bail, Result};
 
 use crate::config::WbConfig;
+use crate::environment;
+use crate::error::WbError;
 use crate::git;
+use crate::hooks::{self, Hook, HookContext};
+use crate::populate;
 use crate::resolve;
+use crate::session;
+use crate::shell;
+use crate::terminal;
+use crate::workspace;
 use crate::worktree;
 
 /// Create a new branch with an associated worktree.
-/// `wb <name> [<start-point>]`
-pub fn run(name: &str, start_point: Option<&str>) -> Result<()> {
+/// `wb <name> [<start-point>]` or `wb create <name> [<start-point>] [--warm[=<branch>]]`
+pub fn run(name: &str, start_point: Option<&str>, warm: Option<Option<&str>>) -> Result<()> {
+    let path = ensure(name, start_point, warm)?;
+    shell::cd(&path);
+    Ok(())
+}
+
+/// Create the branch and/or its worktree if missing, returning the worktree path.
+pub fn ensure(
+    name: &str,
+    start_point: Option<&str>,
+    warm: Option<Option<&str>>,
+) -> Result<PathBuf> {
     let config = WbConfig::load()?;
 
+    let warm_branch;
+    let warm = match warm {
+        Some(Some(branch)) => {
+            warm_branch = resolve::existing_branch(branch)?;
+            if worktree::find_worktree_for_branch(&warm_branch)?.is_none() {
+                return Err(WbError::NoWorktreeForBranch(warm_branch).into());
+            }
+            Some(Some(warm_branch.as_str()))
+        }
+        other => other,
+    };
+
+    // Without a start point, a name matching an existing branch jumps to it
+    let matched = match start_point {
+        Some(_) => None,
+        None => resolve::find_branch(name, true)?,
+    };
+    let name = matched.as_deref().unwrap_or(name);
+
     // Check if branch already exists
     if git::branch_exists(name) {
         // Branch exists — check if it already has a worktree
         if let Some(wt) = worktree::find_worktree_for_branch(name)? {
+            if warm.is_some() {
+                bail!(
+                    "fatal: worktree for '{}' already exists; --warm only applies to new worktrees",
+                    name
+                );
+            }
             // Already has a worktree, just cd to it
-            println!("__wb_cd:{}", wt.path.display());
-            return Ok(());
+            return Ok(wt.path);
         }
         // Branch exists but no worktree — create worktree for it
         let wt_path = resolve::branch_to_worktree_path(&config, name);
         worktree::add_worktree(&wt_path, name, false, None)?;
-        println!("__wb_cd:{}", wt_path.display());
-        return Ok(());
+        prepare_worktree(&config, name, &wt_path, None, warm);
+        return Ok(wt_path);
     }
 
     // Create new branch + worktree
@@ -35,8 +80,89 @@ pub fn run(name: &str, start_point: Option<&str>) -> Result<()> {
         );
     }
 
+    // Branching off a local branch stacks the new branch on it
+    let parent = start_point.filter(|sp| *sp != name && git::branch_exists(sp));
+
+    // Like `git switch`, materialize a unique remote branch of the same name
+    let remote_start;
+    let start_point = match start_point {
+        Some(sp) => Some(sp),
+        None => {
+            remote_start = git::find_unique_remote_branch(name)?;
+            remote_start.as_deref()
+        }
+    };
+
     worktree::add_worktree(&wt_path, name, true, start_point)?;
-    println!("__wb_cd:{}", wt_path.display());
+    if let Some(parent) = parent {
+        git::set_stack_parent(name, parent)?;
+    }
+    prepare_worktree(&config, name, &wt_path, parent, warm);
 
-    Ok(())
+    Ok(wt_path)
+}
+
+/// Copy `wb.copyFiles` and, with `--warm`, clone `wb.warmDirs` from the
+/// source worktree, write `wb.envFile`, run the postCreate hook, then start
+/// a session if `wb.sessionOnCreate` is set.
+///
+/// The worktree exists by now, so a failing step is only reported: stopping
+/// would leave it half set up, and a rerun just jumps to it.
+fn prepare_worktree(
+    config: &WbConfig,
+    name: &str,
+    wt_path: &Path,
+    source_branch: Option<&str>,
+    warm: Option<Option<&str>>,
+) {
+    if !config.copy_files.is_empty() {
+        if let Some(src) = populate::source_worktree(source_branch) {
+            match populate::copy_ignored(&src, wt_path, &config.copy_files) {
+                Ok(0) => {}
+                Ok(copied) => {
+                    eprintln!("Copied {} ignored file(s) from '{}'", copied, src.display())
+                }
+                Err(e) => terminal::warn(&e),
+            }
+        }
+    }
+
+    if let Some(warm_branch) = warm {
+        match populate::source_worktree(warm_branch.or(source_branch)) {
+            Some(src) => match populate::warm(&src, wt_path, &config.warm_dirs) {
+                Ok((dirs, _)) if dirs.is_empty() => {
+                    eprintln!("Nothing to warm from '{}'", src.display())
+                }
+                Ok((dirs, method)) => eprintln!(
+                    "Warmed {} from '{}' ({})",
+                    dirs.join(", "),
+                    src.display(),
+                    method.name()
+                ),
+                Err(e) => terminal::warn(&e),
+            },
+            None => eprintln!("warning: no worktree to warm from"),
+        }
+    }
+
+    if let Err(e) = environment::write_env_file(config, name, wt_path) {
+        terminal::warn(&e);
+    }
+
+    let ctx = HookContext {
+        branch: name,
+        path: Some(wt_path),
+        ..Default::default()
+    };
+    hooks::run_post(Hook::PostCreate, &ctx);
+    workspace::refresh();
+
+    if config.session_on_create {
+        let session = session::session_name(config.multiplexer, name);
+        if !session::exists(config.multiplexer, &session) {
+            if let Err(e) = session::spawn(config, &session, wt_path) {
+                eprintln!("warning: could not start session '{}': {}", session, e);
+            }
+        }
+    }
 }
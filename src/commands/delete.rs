@@ -1,6 +1,11 @@
 use anyhow::{bail, Result};
 
+use crate::config::WbConfig;
+use crate::error::WbError;
 use crate::git;
+use crate::hooks::{self, Hook, HookContext};
+use crate::session;
+use crate::workspace;
 use crate::worktree;
 
 /// Delete one or more branches and their worktrees.
@@ -25,19 +30,92 @@ fn delete_one(name: &str, force: bool) -> Result<()> {
         }
     }
 
+    if !git::branch_exists(name) {
+        return Err(WbError::BranchNotFound(name.to_string()).into());
+    }
+
+    // Refuse before touching the worktree, so a failed safe delete leaves it intact.
+    // A branch that was squash- or rebase-merged into the base counts as merged.
+    let merged = git::is_merged(name);
+    if !force && !merged && !landed_in_base(name) {
+        return Err(WbError::BranchNotFullyMerged(name.to_string()).into());
+    }
+
+    // A failing preDelete hook vetoes the delete
+    pre_delete(name)?;
+    remove(name, force, merged)
+}
+
+/// Run the preDelete hook for `name`; a failing hook vetoes the delete.
+pub fn pre_delete(name: &str) -> Result<()> {
+    let wt = worktree::find_worktree_for_branch(name)?;
+    let ctx = HookContext {
+        branch: name,
+        path: wt.as_ref().map(|wt| wt.path.as_path()),
+        ..Default::default()
+    };
+    hooks::run(Hook::PreDelete, &ctx)
+}
+
+/// Delete a branch `wb land` has just merged into `base`. Its upstream and
+/// the configured base branch may both lag behind, so only `base` is checked.
+pub fn remove_landed(name: &str, base: &str) -> Result<()> {
+    if !git::is_landed(name, base) {
+        return Err(WbError::BranchNotFullyMerged(name.to_string()).into());
+    }
+    remove(name, false, false)
+}
+
+/// Remove the branch and its worktree once the checks and the preDelete hook passed.
+fn remove(name: &str, force: bool, merged: bool) -> Result<()> {
+    let wt = worktree::find_worktree_for_branch(name)?;
+    let was = short_hash(name);
+    let parent = git::stack_parent(name);
+
     // Remove worktree if one exists
-    if let Some(wt) = worktree::find_worktree_for_branch(name)? {
+    if let Some(wt) = wt {
         worktree::remove_worktree(&wt.path, force)?;
     }
 
-    // Delete the branch ref
-    git::delete_branch(name, force)?;
+    // Delete the branch ref; `git branch -d` cannot see squash merges,
+    // so a landed branch has to be deleted with -D
+    git::delete_branch(name, force || !merged)?;
+
+    // Branches stacked on this one move down to its parent
+    for (child, child_parent) in git::stack_parents() {
+        if child_parent == name {
+            git::reparent(&child, parent.as_deref())?;
+        }
+    }
+
+    eprintln!("Deleted branch {} (was {}).", name, was);
+    workspace::refresh();
 
-    eprintln!("Deleted branch {} (was {}).", name, short_hash(name));
+    if let Ok(config) = WbConfig::load() {
+        if config.session_on_delete {
+            let session = session::session_name(config.multiplexer, name);
+            if let Err(e) = session::kill(config.multiplexer, &session) {
+                eprintln!("warning: could not kill session '{}': {}", session, e);
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Whether the branch's changes have landed in its base: the stack parent
+/// for a stacked branch, the configured base branch otherwise.
+fn landed_in_base(name: &str) -> bool {
+    let base = match git::stack_parent(name).filter(|p| git::branch_exists(p)) {
+        Some(parent) => parent,
+        None => match WbConfig::load().and_then(|c| c.base_branch()) {
+            Ok(base) => base,
+            Err(_) => return false,
+        },
+    };
+    base != name && git::is_landed(name, &base)
+}
+
 fn short_hash(branch: &str) -> String {
     git::run(&["rev-parse", "--short", &format!("refs/heads/{}", branch)])
         .unwrap_or_else(|_| "unknown".to_string())
@@ -0,0 +1,43 @@
+use anyhow::{bail, Result};
+
+use crate::config::WbConfig;
+use crate::environment;
+use crate::error::WbError;
+use crate::resolve;
+use crate::worktree;
+
+/// Print a worktree's variables as `KEY=value` lines, allocating its port range if needed.
+/// `wb env [<branch>] [--write]`
+pub fn run(branch: Option<&str>, write: bool) -> Result<()> {
+    let config = WbConfig::load()?;
+    let wt = match branch {
+        Some(name) => {
+            let name = resolve::existing_branch(name)?;
+            match worktree::find_worktree_for_branch(&name)? {
+                Some(wt) => wt,
+                None => return Err(WbError::NoWorktreeForBranch(name).into()),
+            }
+        }
+        None => {
+            let cwd = std::env::current_dir()?;
+            match worktree::find_worktree_for_path(&cwd)? {
+                Some(wt) if wt.branch.is_some() => wt,
+                _ => bail!("fatal: not on any branch"),
+            }
+        }
+    };
+    let name = wt.branch.as_deref().unwrap_or_default();
+
+    if write {
+        if config.env_file.is_none() {
+            bail!("fatal: no env file configured; set it with 'git config wb.envFile .env'");
+        }
+        environment::write_env_file(&config, name, &wt.path)?;
+    }
+
+    for line in environment::format_vars(&environment::vars(&config, name, &wt.path)?) {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::worktree::{self, WorktreeInfo};
+
+/// Result of running the command in one worktree.
+enum Outcome {
+    Passed,
+    /// Exit code, or `None` if the process was killed by a signal.
+    Failed(Option<i32>),
+    /// The command could not be started.
+    Error(String),
+    /// Not run because an earlier worktree failed (without `--keep-going`).
+    Skipped,
+}
+
+/// Run a command in every worktree, in parallel, prefixing output with the branch.
+/// `wb foreach [--pattern <glob>] [--jobs N] [--keep-going] -- <cmd>...`
+///
+/// A single argument is run through `sh -c`; multiple arguments are executed directly.
+pub fn run(
+    command: &[String],
+    pattern: Option<&str>,
+    jobs: Option<usize>,
+    keep_going: bool,
+) -> Result<()> {
+    let worktrees: Vec<WorktreeInfo> = worktree::list_worktrees()?
+        .into_iter()
+        .filter(|wt| !wt.is_bare && wt.prunable.is_none())
+        .filter(|wt| match pattern {
+            Some(p) => wt
+                .branch
+                .as_deref()
+                .is_some_and(|b| glob_match::glob_match(p, b)),
+            None => true,
+        })
+        .collect();
+
+    if worktrees.is_empty() {
+        bail!("fatal: no worktrees to run in");
+    }
+
+    let jobs = jobs
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .clamp(1, worktrees.len());
+
+    let queue = Mutex::new((0..worktrees.len()).collect::<VecDeque<_>>());
+    let outcomes = Mutex::new(
+        (0..worktrees.len())
+            .map(|_| Outcome::Skipped)
+            .collect::<Vec<_>>(),
+    );
+    let stop = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Some(i) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let outcome = run_in_worktree(command, &worktrees[i]);
+                if !matches!(outcome, Outcome::Passed) && !keep_going {
+                    stop.store(true, Ordering::SeqCst);
+                }
+                outcomes.lock().unwrap()[i] = outcome;
+            });
+        }
+    });
+
+    let outcomes = outcomes.into_inner().unwrap();
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    eprintln!();
+    for (wt, outcome) in worktrees.iter().zip(&outcomes) {
+        let label = label(wt);
+        match outcome {
+            Outcome::Passed => {
+                passed += 1;
+                eprintln!("{}  {}", "ok  ".green(), label);
+            }
+            Outcome::Failed(code) => {
+                failed += 1;
+                let code = code.map_or("signal".to_string(), |c| format!("exit {}", c));
+                eprintln!("{}  {} ({})", "FAIL".red(), label, code);
+            }
+            Outcome::Error(e) => {
+                failed += 1;
+                eprintln!("{}  {} ({})", "FAIL".red(), label, e);
+            }
+            Outcome::Skipped => {
+                skipped += 1;
+                eprintln!("{}  {}", "skip".dimmed(), label);
+            }
+        }
+    }
+
+    let mut summary = format!("{} succeeded, {} failed", passed, failed);
+    if skipped > 0 {
+        summary.push_str(&format!(", {} skipped", skipped));
+    }
+    eprintln!("{}", summary);
+
+    if failed > 0 {
+        bail!("error: command failed in {} worktree(s)", failed);
+    }
+
+    Ok(())
+}
+
+/// Branch name, or the directory name for a detached worktree.
+fn label(wt: &WorktreeInfo) -> String {
+    wt.branch.clone().unwrap_or_else(|| {
+        wt.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    })
+}
+
+fn run_in_worktree(command: &[String], wt: &WorktreeInfo) -> Outcome {
+    let label = label(wt);
+    let mut cmd = build_command(command, &wt.path);
+    cmd.env("WB_BRANCH", wt.branch.as_deref().unwrap_or(""))
+        .env("WB_WORKTREE", &wt.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return Outcome::Error(e.to_string()),
+    };
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    thread::scope(|s| {
+        s.spawn(|| forward_lines(stdout, &label, false));
+        s.spawn(|| forward_lines(stderr, &label, true));
+    });
+
+    match child.wait() {
+        Ok(status) if status.success() => Outcome::Passed,
+        Ok(status) => Outcome::Failed(status.code()),
+        Err(e) => Outcome::Error(e.to_string()),
+    }
+}
+
+fn build_command(command: &[String], dir: &Path) -> Command {
+    let mut cmd = if let [script] = command {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        cmd
+    } else {
+        let mut cmd = Command::new(&command[0]);
+        cmd.args(&command[1..]);
+        cmd
+    };
+    cmd.current_dir(dir);
+    cmd
+}
+
+/// Copy lines from a child's output to ours, each prefixed with `[label]`.
+/// Reads until EOF even past invalid UTF-8, so the child never hits a closed pipe.
+fn forward_lines(reader: impl Read, label: &str, to_stderr: bool) {
+    let prefix = format!("[{}]", label).cyan();
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let bytes = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+        let line = String::from_utf8_lossy(bytes);
+        if to_stderr {
+            eprintln!("{} {}", prefix, line);
+        } else {
+            println!("{} {}", prefix, line);
+        }
+    }
+}
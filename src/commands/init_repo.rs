@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{bail, Context, Result};
 
 use crate::git;
+use crate::shell;
 
 /// Handle `wb init <target>` — either shell integration or repo clone/conversion.
 pub fn run(target: Option<&str>, directory: Option<&str>) -> Result<()> {
@@ -66,6 +67,20 @@ fn clone_bare(url: &str, directory: Option<&str>) -> Result<()> {
     // Fetch to populate remotes
     git::run_in(&bare_dir, &["fetch", "origin"])?;
 
+    // A bare clone copies the remote's branches without tracking config;
+    // point each at its origin counterpart so `wb sync`/`wb status` can use it
+    let branches = git::run_in(
+        &bare_dir,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads/"],
+    )?;
+    for branch in branches.lines() {
+        let upstream = format!("origin/{}", branch);
+        let _ = git::run_in(
+            &bare_dir,
+            &["branch", "--set-upstream-to", &upstream, branch],
+        );
+    }
+
     // Create worktree for default branch
     let worktree_path = dir.join(&default_branch);
     git::run_in(
@@ -80,7 +95,7 @@ fn clone_bare(url: &str, directory: Option<&str>) -> Result<()> {
 
     // Output cd directive for the shell wrapper
     let canonical = worktree_path.canonicalize().unwrap_or(worktree_path);
-    println!("__wb_cd:{}", canonical.display());
+    shell::cd(&canonical);
 
     Ok(())
 }
@@ -210,7 +225,7 @@ fn convert_existing() -> Result<()> {
 
     // Output cd directive
     let canonical = worktree_path.canonicalize().unwrap_or(worktree_path);
-    println!("__wb_cd:{}", canonical.display());
+    shell::cd(&canonical);
 
     Ok(())
 }
@@ -0,0 +1,137 @@
+use anyhow::{bail, Result};
+
+use crate::commands::delete;
+use crate::config::{LandStrategy, WbConfig};
+use crate::error::WbError;
+use crate::git;
+use crate::resolve;
+use crate::shell;
+use crate::worktree::{self, WorktreeInfo};
+
+/// Integrate a branch into its base branch's worktree, then delete the branch
+/// and its worktree and cd to the base.
+/// `wb land [<branch>] [--base <base>] [--strategy ff|merge|rebase]`
+pub fn run(branch: Option<&str>, base: Option<&str>, strategy: Option<LandStrategy>) -> Result<()> {
+    let config = WbConfig::load()?;
+    let branch = match branch {
+        Some(name) => resolve::confirmed_branch(name)?,
+        None => current_branch_from_cwd()?,
+    };
+    // A stacked branch lands on its parent
+    let base = match base {
+        Some(name) => resolve::confirmed_branch(name)?,
+        None => match git::stack_parent(&branch).filter(|p| git::branch_exists(p)) {
+            Some(parent) => parent,
+            None => config.base_branch()?,
+        },
+    };
+    let strategy = strategy.unwrap_or(config.land_strategy);
+
+    if branch == base {
+        bail!("fatal: cannot land '{}' into itself", branch);
+    }
+    if !git::branch_exists(&branch) {
+        return Err(WbError::BranchNotFound(branch).into());
+    }
+
+    let Some(base_wt) = worktree::find_worktree_for_branch(&base)? else {
+        return Err(WbError::NoWorktreeForBranch(base).into());
+    };
+    ensure_clean(&base_wt, false)?;
+
+    let branch_wt = worktree::find_worktree_for_branch(&branch)?;
+    if let Some(ref wt) = branch_wt {
+        // Its worktree is removed afterwards, which untracked files would block
+        ensure_clean(wt, true)?;
+    }
+
+    // The preDelete hook gets its veto before anything is merged, so a
+    // refusal cannot leave the branch landed but not deleted
+    delete::pre_delete(&branch)?;
+
+    match strategy {
+        LandStrategy::Ff => {
+            git::run_in(&base_wt.path, &["merge", "--ff-only", &branch])?;
+        }
+        LandStrategy::Merge => {
+            if let Err(e) = git::run_in(&base_wt.path, &["merge", "--no-ff", "--no-edit", &branch])
+            {
+                let _ = git::run_in(&base_wt.path, &["merge", "--abort"]);
+                bail!(
+                    "{}\nerror: merge of '{}' into '{}' failed and was aborted",
+                    e,
+                    branch,
+                    base
+                );
+            }
+        }
+        LandStrategy::Rebase => {
+            let Some(ref wt) = branch_wt else {
+                bail!(
+                    "fatal: the rebase strategy needs a worktree for '{}'",
+                    branch
+                );
+            };
+            if let Err(e) = git::run_in(&wt.path, &["rebase", &base]) {
+                let _ = git::run_in(&wt.path, &["rebase", "--abort"]);
+                bail!(
+                    "{}\nerror: rebase of '{}' onto '{}' failed and was aborted",
+                    e,
+                    branch,
+                    base
+                );
+            }
+            git::run_in(&base_wt.path, &["merge", "--ff-only", &branch])?;
+        }
+    }
+
+    eprintln!("Landed '{}' into '{}'.", branch, base);
+
+    // Step into the base worktree first, so the branch's worktree can be
+    // removed even when we were standing in it
+    std::env::set_current_dir(&base_wt.path)?;
+    delete::remove_landed(&branch, &base)?;
+
+    shell::cd(&base_wt.path);
+
+    Ok(())
+}
+
+/// Refuse to land when a worktree has uncommitted changes or an operation in
+/// progress; with `untracked`, untracked files count as changes too.
+fn ensure_clean(wt: &WorktreeInfo, untracked: bool) -> Result<()> {
+    let status = worktree::status(&wt.path)?;
+    let name = wt.branch.as_deref().unwrap_or("HEAD");
+    if let Some(op) = status.operation {
+        bail!(
+            "fatal: a {} is in progress in '{}'",
+            op.name(),
+            wt.path.display()
+        );
+    }
+    if status.staged > 0 || status.modified > 0 || status.conflicted > 0 {
+        bail!(
+            "fatal: worktree for '{}' has uncommitted changes ({})",
+            name,
+            wt.path.display()
+        );
+    }
+    if untracked && status.untracked > 0 {
+        bail!(
+            "fatal: worktree for '{}' has untracked files ({})",
+            name,
+            wt.path.display()
+        );
+    }
+    Ok(())
+}
+
+fn current_branch_from_cwd() -> Result<String> {
+    let cwd = std::env::current_dir()?;
+    if let Some(wt) = worktree::find_worktree_for_path(&cwd)? {
+        if let Some(branch) = wt.branch {
+            return Ok(branch);
+        }
+    }
+    bail!("fatal: not on any branch");
+}
@@ -1,29 +1,94 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
-use crate::git::{self, BranchFilter};
-use crate::output;
+use crate::config::WbConfig;
+use crate::git::{self, BranchFilter, BranchScope};
+use crate::output::{self, ListStyle, WorktreeView};
+use crate::template::Template;
 use crate::worktree;
 
-/// List local branches.
-pub fn run() -> Result<()> {
-    let filter = BranchFilter::default();
+/// List branches.
+/// `wb list [-r | -a] [-v | -vv | --json | --porcelain | --format <fmt> | --stack]` or `wb -l [<pattern>...]`
+pub fn run(filter: BranchFilter, style: ListStyle) -> Result<()> {
+    // Without an explicit output option, fall back to the wb.listFormat template
+    let style = match style {
+        ListStyle::Human => match WbConfig::load()?.list_format {
+            Some(format) => ListStyle::Template(Template::parse(&format)?),
+            None => ListStyle::Human,
+        },
+        style => style,
+    };
 
-    // Detect current branch from cwd
+    let with_remotes = filter.scope != BranchScope::Local;
     let mut branches = git::list_branches(filter)?;
+    if with_remotes {
+        git::link_local_branches(&mut branches)?;
+    }
 
     // Mark which branch is "current" based on cwd → worktree
     if let Ok(cwd) = std::env::current_dir() {
         if let Ok(Some(wt)) = worktree::find_worktree_for_path(&cwd) {
             if let Some(ref branch_name) = wt.branch {
                 for b in &mut branches {
-                    b.is_head = b.name == *branch_name;
+                    b.is_head = !b.is_remote && b.name == *branch_name;
                 }
             }
         }
     }
 
     let worktrees = worktree::list_worktrees().unwrap_or_default();
-    let output = output::format_branch_list(&branches, &worktrees);
+    let output = match style {
+        ListStyle::Human => output::format_branch_list(&branches, &worktrees),
+        ListStyle::Verbose(level) => output::format_branch_verbose(&branches, &worktrees, level),
+        ListStyle::Json => output::format_branch_json(&branches, &worktrees),
+        ListStyle::Porcelain => output::format_branch_porcelain(&branches, &worktrees),
+        ListStyle::Template(ref template) => {
+            output::format_branch_template(&branches, &worktrees, template)
+        }
+        ListStyle::Stack => {
+            output::format_branch_stack(&branches, &git::stack_parents(), &worktrees)
+        }
+    };
+
+    if !output.is_empty() {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
+
+/// List every worktree with its HEAD and state, plus stray directories.
+/// `wb list --worktrees [--json]`
+pub fn run_worktrees(style: ListStyle) -> Result<()> {
+    let config = WbConfig::load()?;
+    let worktrees: Vec<_> = worktree::list_worktrees()?
+        .into_iter()
+        .filter(|wt| !wt.is_bare)
+        .collect();
+
+    let current = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| worktree::find_worktree_for_path(&cwd).ok().flatten());
+
+    let views: Vec<WorktreeView> = worktrees
+        .iter()
+        .map(|wt| WorktreeView {
+            info: wt,
+            is_current: current.as_ref().is_some_and(|c| c.path == wt.path),
+            is_orphaned: wt.branch.as_deref().is_some_and(|b| !git::branch_exists(b)),
+        })
+        .collect();
+
+    let stray = if config.worktree_dir.is_dir() {
+        worktree::find_stray_dirs(&config.worktree_dir, &worktrees)?
+    } else {
+        Vec::new()
+    };
+
+    let output = match style {
+        ListStyle::Human => output::format_worktree_list(&views, &stray),
+        ListStyle::Json => output::format_worktree_json(&views, &stray),
+        _ => bail!("fatal: --worktrees only supports the default and --json output"),
+    };
 
     if !output.is_empty() {
         println!("{}", output);
@@ -1,6 +1,20 @@
+pub mod back;
+pub mod clean;
 pub mod copy;
 pub mod create;
 pub mod delete;
+pub mod env;
+pub mod foreach;
 pub mod init_repo;
+pub mod land;
 pub mod list;
+pub mod open;
+pub mod recent;
 pub mod rename;
+pub mod restack;
+pub mod session;
+pub mod show_current;
+pub mod status;
+pub mod sync;
+pub mod ui;
+pub mod workspace;
@@ -0,0 +1,53 @@
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::commands::create;
+use crate::config::WbConfig;
+use crate::resolve;
+use crate::terminal;
+use crate::worktree;
+
+/// Open a branch's worktree in the editor, creating the worktree if needed.
+/// `wb open [<branch>]`
+///
+/// The editor is `wb.editor`, else `$VISUAL`, else `$EDITOR`; it is run
+/// through `sh` so it may include arguments (e.g. `code --new-window`).
+pub fn run(branch: Option<&str>) -> Result<()> {
+    let config = WbConfig::load()?;
+    let editor = config
+        .editor
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty());
+    let Some(editor) = editor else {
+        bail!("fatal: no editor configured; set $VISUAL or 'git config wb.editor <command>'");
+    };
+
+    // Only the worktree is created; the branch has to exist
+    let path = match branch {
+        Some(name) => create::ensure(&resolve::existing_branch(name)?, None, None)?,
+        None => {
+            let cwd = std::env::current_dir()?;
+            match worktree::find_worktree_for_path(&cwd)? {
+                Some(wt) if !wt.is_bare => wt.path,
+                _ => bail!("fatal: not inside a worktree"),
+            }
+        }
+    };
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&path);
+    terminal::stdout_to_tty(&mut cmd);
+
+    let status = cmd
+        .status()
+        .with_context(|| format!("failed to run editor '{}'", editor))?;
+    if !status.success() {
+        bail!("fatal: editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
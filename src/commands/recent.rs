@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use crate::history;
+use crate::shell;
+use crate::terminal;
+use crate::worktree;
+
+/// List recently visited worktrees, or cd into one: the `<n>`th, or the one
+/// picked at a prompt when run interactively.
+/// `wb recent [<n>] [--list]`
+pub fn run(index: Option<usize>, list: bool) -> Result<()> {
+    let entries = history::recent();
+    if entries.is_empty() {
+        bail!("fatal: no recently visited worktrees");
+    }
+    let labels = labels(&entries)?;
+
+    let choice = match index {
+        Some(n) => match n.checked_sub(1).filter(|i| *i < entries.len()) {
+            Some(i) => i,
+            None => bail!(
+                "fatal: no worktree #{} in history ({} remembered)",
+                n,
+                entries.len()
+            ),
+        },
+        None if !list && terminal::is_interactive() => {
+            match terminal::choose("Recent worktrees:", &labels)? {
+                Some(i) => i,
+                None => bail!("Aborted."),
+            }
+        }
+        None => {
+            for (i, label) in labels.iter().enumerate() {
+                println!("{:>3}  {}", i + 1, label);
+            }
+            return Ok(());
+        }
+    };
+
+    shell::cd(&entries[choice]);
+    Ok(())
+}
+
+/// `<branch>  <path>` for each entry, with branch names aligned.
+fn labels(entries: &[PathBuf]) -> Result<Vec<String>> {
+    let worktrees = worktree::list_worktrees()?;
+    let names: Vec<String> = entries
+        .iter()
+        .map(|path| {
+            worktrees
+                .iter()
+                .find(|wt| wt.path.canonicalize().is_ok_and(|p| p == *path))
+                .and_then(|wt| wt.branch.clone())
+                .unwrap_or_else(|| "(detached)".to_string())
+        })
+        .collect();
+    let width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+
+    Ok(names
+        .iter()
+        .zip(entries)
+        .map(|(name, path)| format!("{:<width$}  {}", name, path.display(), width = width))
+        .collect())
+}
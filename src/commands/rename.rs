@@ -1,24 +1,36 @@
 use anyhow::{bail, Result};
 
 use crate::config::WbConfig;
+use crate::environment;
 use crate::git;
+use crate::hooks::{self, Hook, HookContext};
 use crate::resolve;
+use crate::terminal;
+use crate::workspace;
 use crate::worktree;
 
 /// Rename a branch and move its worktree.
-/// `wb rename <new> [<old>]`
-pub fn run(new_name: &str, old_name: Option<&str>) -> Result<()> {
+/// `wb rename <new> [<old>]` or `wb -m [<old>] <new>`
+pub fn run(new_name: &str, old_name: Option<&str>, force: bool) -> Result<()> {
     let old_name = match old_name {
-        Some(name) => name.to_string(),
+        Some(name) => resolve::confirmed_branch(name)?,
         None => current_branch_from_cwd()?,
     };
 
     let config = WbConfig::load()?;
 
     // Rename the git branch ref
-    git::rename_branch(&old_name, new_name, false)?;
+    git::rename_branch(&old_name, new_name, force)?;
+
+    // Keep branches stacked on the old name attached
+    for (child, parent) in git::stack_parents() {
+        if parent == old_name {
+            git::reparent(&child, Some(new_name))?;
+        }
+    }
 
     // Move the worktree if one exists
+    let mut paths = None;
     if let Some(wt) = worktree::find_worktree_for_branch(new_name)? {
         // Branch ref already renamed, worktree still points to old path
         let new_path = resolve::branch_to_worktree_path(&config, new_name);
@@ -33,10 +45,24 @@ pub fn run(new_name: &str, old_name: Option<&str>) -> Result<()> {
         } else {
             eprintln!("Branch '{}' renamed to '{}'", old_name, new_name);
         }
+        // The worktree has moved by now, so a failure is only reported
+        if let Err(e) = environment::write_env_file(&config, new_name, &new_path) {
+            terminal::warn(&e);
+        }
+        paths = Some((wt.path, new_path));
     } else {
         eprintln!("Branch '{}' renamed to '{}'", old_name, new_name);
     }
 
+    let ctx = HookContext {
+        branch: new_name,
+        path: paths.as_ref().map(|(_, new)| new.as_path()),
+        old_branch: Some(&old_name),
+        old_path: paths.as_ref().map(|(old, _)| old.as_path()),
+    };
+    hooks::run_post(Hook::PostRename, &ctx);
+    workspace::refresh();
+
     Ok(())
 }
 
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+
+use crate::git;
+use crate::worktree;
+
+/// Rebase every stacked branch onto its parent, parents first.
+/// `wb restack`
+///
+/// Each branch is rebased in its own worktree, replaying only the commits
+/// made since it was last stacked on the parent. Branches without a worktree
+/// or with local changes are skipped; a conflict stops the restack with the
+/// rebase left in progress for the user to resolve.
+pub fn run() -> Result<()> {
+    let parents: HashMap<String, String> = git::stack_parents()
+        .into_iter()
+        .filter(|(child, _)| git::branch_exists(child))
+        .collect();
+
+    let mut order = Vec::new();
+    let mut children: Vec<&String> = parents.keys().collect();
+    children.sort();
+    for child in children {
+        visit(child, &parents, &mut order);
+    }
+
+    for child in order {
+        let parent = &parents[child];
+        restack_one(child, parent)?;
+    }
+
+    Ok(())
+}
+
+/// Append `branch` to `order` after its stacked ancestors.
+fn visit<'a>(
+    branch: &'a String,
+    parents: &'a HashMap<String, String>,
+    order: &mut Vec<&'a String>,
+) {
+    // Collect unordered ancestors that are themselves stacked, stopping at a cycle
+    let mut chain = vec![branch];
+    while let Some((parent, _)) = parents.get_key_value(&parents[*chain.last().unwrap()]) {
+        if chain.contains(&parent) || order.contains(&parent) {
+            break;
+        }
+        chain.push(parent);
+    }
+    for b in chain.into_iter().rev() {
+        if !order.contains(&b) {
+            order.push(b);
+        }
+    }
+}
+
+fn restack_one(branch: &str, parent: &str) -> Result<()> {
+    if !git::branch_exists(parent) {
+        report_skip(branch, &format!("parent {} does not exist", parent));
+        return Ok(());
+    }
+
+    let parent_ref = format!("refs/heads/{}", parent);
+    let branch_ref = format!("refs/heads/{}", branch);
+    let parent_sha = git::run(&["rev-parse", &parent_ref])?;
+
+    if git::is_ancestor(&parent_ref, &branch_ref) {
+        git::set_stack_base(branch, &parent_sha)?;
+        return Ok(());
+    }
+
+    let Some(wt) = worktree::find_worktree_for_branch(branch)? else {
+        report_skip(branch, "no worktree");
+        return Ok(());
+    };
+    let status = worktree::status(&wt.path)?;
+    if let Some(op) = status.operation {
+        report_skip(branch, &format!("{} in progress", op.name()));
+        return Ok(());
+    }
+    if status.staged > 0 || status.modified > 0 || status.conflicted > 0 {
+        report_skip(branch, "worktree has local changes");
+        return Ok(());
+    }
+
+    // Replay only the branch's own commits when the old parent commit is known
+    let base = git::stack_base(branch).filter(|b| git::is_ancestor(b, &branch_ref));
+    let args = match base {
+        Some(ref base) => vec!["rebase", "--quiet", "--onto", &parent_ref, base.as_str()],
+        None => vec!["rebase", "--quiet", &parent_ref],
+    };
+    if let Err(e) = git::run_in(&wt.path, &args) {
+        bail!(
+            "{}\nerror: conflict while rebasing '{}' onto '{}' in {}\n\
+             hint: resolve the conflict and run 'git rebase --continue' there (or 'git rebase --abort'),\n\
+             hint: then run 'wb restack' again to restack the branches above it",
+            e,
+            branch,
+            parent,
+            wt.path.display()
+        );
+    }
+
+    git::set_stack_base(branch, &parent_sha)?;
+    eprintln!("{} {} onto {}", "restacked".green(), branch, parent);
+    Ok(())
+}
+
+fn report_skip(branch: &str, reason: &str) {
+    eprintln!("{} {} ({})", "skipped".yellow(), branch, reason);
+}
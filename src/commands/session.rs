@@ -0,0 +1,32 @@
+use anyhow::{bail, Result};
+
+use crate::config::WbConfig;
+use crate::error::WbError;
+use crate::resolve;
+use crate::session;
+use crate::worktree;
+
+/// Attach to the branch's multiplexer session, creating it in the worktree if needed.
+/// `wb session [<branch>]`
+pub fn run(branch: Option<&str>) -> Result<()> {
+    let config = WbConfig::load()?;
+    let wt = match branch {
+        Some(name) => {
+            let name = resolve::existing_branch(name)?;
+            match worktree::find_worktree_for_branch(&name)? {
+                Some(wt) => wt,
+                None => return Err(WbError::NoWorktreeForBranch(name).into()),
+            }
+        }
+        None => {
+            let cwd = std::env::current_dir()?;
+            match worktree::find_worktree_for_path(&cwd)? {
+                Some(wt) if wt.branch.is_some() => wt,
+                _ => bail!("fatal: not on any branch"),
+            }
+        }
+    };
+    let name = session::session_name(config.multiplexer, wt.branch.as_deref().unwrap_or_default());
+
+    session::attach(&config, &name, &wt.path)
+}
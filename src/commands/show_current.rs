@@ -0,0 +1,17 @@
+use anyhow::Result;
+
+use crate::worktree;
+
+/// Print the branch of the worktree containing the current directory.
+/// `wb --show-current`
+///
+/// Like `git branch --show-current`, prints nothing when detached.
+pub fn run() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    if let Some(wt) = worktree::find_worktree_for_path(&cwd)? {
+        if let Some(branch) = wt.branch {
+            println!("{}", branch);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+
+use crate::git;
+use crate::output::{self, StatusView};
+use crate::worktree;
+
+/// Show a dashboard of every worktree: changes, upstream, stashes and
+/// in-progress operations.
+/// `wb status`
+pub fn run() -> Result<()> {
+    let worktrees: Vec<_> = worktree::list_worktrees()?
+        .into_iter()
+        .filter(|wt| !wt.is_bare)
+        .collect();
+    let stashes = git::stash_counts().unwrap_or_default();
+
+    let current = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| worktree::find_worktree_for_path(&cwd).ok().flatten());
+
+    let views: Vec<StatusView> = worktrees
+        .iter()
+        .map(|wt| StatusView {
+            info: wt,
+            status: worktree::status(&wt.path).ok(),
+            stashes: wt
+                .branch
+                .as_ref()
+                .and_then(|b| stashes.get(b))
+                .copied()
+                .unwrap_or(0),
+            is_current: current.as_ref().is_some_and(|c| c.path == wt.path),
+        })
+        .collect();
+
+    let output = output::format_status_list(&views);
+    if !output.is_empty() {
+        println!("{}", output);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::git::{self, BranchFilter, BranchInfo};
+use crate::worktree::{self, WorktreeInfo};
+
+/// Fetch all remotes, then fast-forward every branch that is strictly behind
+/// its upstream.
+/// `wb sync`
+///
+/// Branches checked out in a worktree are fast-forwarded there with
+/// `merge --ff-only` when the worktree has no local changes; other branches
+/// are moved with `update-ref`. Dirty, diverged and gone branches are skipped.
+pub fn run() -> Result<()> {
+    eprintln!("Fetching all remotes...");
+    git::run(&["fetch", "--all", "--prune"])?;
+
+    let branches = git::list_branches(BranchFilter::default())?;
+    let worktrees = worktree::list_worktrees()?;
+
+    for branch in &branches {
+        let Some(ref upstream) = branch.upstream else {
+            continue;
+        };
+        let tracking = &branch.tracking;
+
+        if tracking.gone {
+            report_skip(branch, &format!("upstream {} is gone", upstream));
+        } else if tracking.behind == 0 {
+            continue;
+        } else if tracking.ahead > 0 {
+            report_skip(
+                branch,
+                &format!(
+                    "diverged from {}: ahead {}, behind {}",
+                    upstream, tracking.ahead, tracking.behind
+                ),
+            );
+        } else {
+            let wt = worktrees
+                .iter()
+                .find(|wt| wt.branch.as_deref() == Some(&branch.name) && !wt.is_bare);
+            match fast_forward(branch, upstream, wt) {
+                Ok(new_sha) => eprintln!(
+                    "{} {} {}..{}",
+                    "updated".green(),
+                    branch.name,
+                    branch.short_sha,
+                    new_sha
+                ),
+                Err(reason) => report_skip(branch, &reason),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fast-forward `branch` to `upstream`, returning the new short sha or why it was skipped.
+fn fast_forward(
+    branch: &BranchInfo,
+    upstream: &str,
+    wt: Option<&WorktreeInfo>,
+) -> std::result::Result<String, String> {
+    match wt {
+        Some(wt) => {
+            let status = worktree::status(&wt.path).map_err(|e| e.to_string())?;
+            if let Some(op) = status.operation {
+                return Err(format!("{} in progress", op.name()));
+            }
+            if status.staged > 0 || status.modified > 0 || status.conflicted > 0 {
+                return Err("worktree has local changes".to_string());
+            }
+            git::run_in(&wt.path, &["merge", "--ff-only", "--quiet", upstream])
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            let new = git::run(&["rev-parse", "--verify", upstream]).map_err(|e| e.to_string())?;
+            let refname = format!("refs/heads/{}", branch.name);
+            let message = format!("wb sync: fast-forward to {}", upstream);
+            git::run(&["update-ref", "-m", &message, &refname, &new, &branch.sha])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    git::run(&[
+        "rev-parse",
+        "--short",
+        &format!("refs/heads/{}", branch.name),
+    ])
+    .map_err(|e| e.to_string())
+}
+
+fn report_skip(branch: &BranchInfo, reason: &str) {
+    eprintln!("{} {} ({})", "skipped".yellow(), branch.name, reason);
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+
+use crate::shell;
+use crate::ui;
+
+/// Browse and manage branches in a full-screen dashboard; switching to a
+/// branch cds into its worktree on exit.
+/// `wb ui`
+pub fn run() -> Result<()> {
+    if let Some(path) = ui::run()? {
+        shell::cd(&path);
+    }
+    Ok(())
+}
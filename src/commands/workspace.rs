@@ -0,0 +1,16 @@
+use anyhow::Result;
+
+use crate::config::WbConfig;
+use crate::workspace;
+
+/// Write an editor workspace file listing every worktree as a root folder.
+/// `wb workspace`
+///
+/// Once the file exists, creating, copying, renaming and deleting branches
+/// keep it up to date.
+pub fn run() -> Result<()> {
+    let config = WbConfig::load()?;
+    let file = workspace::write(&config)?;
+    println!("{}", file.display());
+    Ok(())
+}
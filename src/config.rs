@@ -16,6 +16,35 @@ pub enum NamingConvention {
     Prefixed,
 }
 
+/// How `wb land` integrates a branch into its base.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum LandStrategy {
+    /// Fast-forward the base; fails if the branch is not ahead of it.
+    #[default]
+    Ff,
+    /// Create a merge commit on the base.
+    Merge,
+    /// Rebase the branch onto the base, then fast-forward.
+    Rebase,
+}
+
+/// Terminal multiplexer used by `wb session`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Multiplexer {
+    #[default]
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    pub fn name(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Zellij => "zellij",
+        }
+    }
+}
+
 /// Configuration for wb read from git config.
 #[derive(Debug, Clone)]
 pub struct WbConfig {
@@ -23,6 +52,36 @@ pub struct WbConfig {
     pub worktree_dir: PathBuf,
     /// Naming convention for worktree directories.
     pub naming: NamingConvention,
+    /// Default `wb list --format` template.
+    pub list_format: Option<String>,
+    /// Base branch that merged branches are compared against (`wb.base`).
+    pub base: Option<String>,
+    /// Default strategy for `wb land` (`wb.landStrategy`).
+    pub land_strategy: LandStrategy,
+    /// Globs of ignored files copied into new worktrees (`wb.copyFiles`).
+    pub copy_files: Vec<String>,
+    /// Ignored directories cloned by `wb create --warm` (`wb.warmDirs`).
+    pub warm_dirs: Vec<String>,
+    /// File in each worktree that per-worktree variables are written to (`wb.envFile`).
+    pub env_file: Option<String>,
+    /// First port handed out to worktrees (`wb.portBase`).
+    pub port_base: u16,
+    /// Number of ports reserved per worktree (`wb.portSpan`).
+    pub port_span: u16,
+    /// Multiplexer for `wb session` (`wb.multiplexer`, else detected from the environment).
+    pub multiplexer: Multiplexer,
+    /// Extra tmux windows as `name=command` (`wb.sessionWindows`).
+    pub session_windows: Vec<String>,
+    /// Zellij layout for new sessions (`wb.sessionLayout`).
+    pub session_layout: Option<String>,
+    /// Start a detached session when `wb create` adds a worktree (`wb.sessionOnCreate`).
+    pub session_on_create: bool,
+    /// Kill the branch's session when it is deleted (`wb.sessionOnDelete`).
+    pub session_on_delete: bool,
+    /// Editor command for `wb open` (`wb.editor`).
+    pub editor: Option<String>,
+    /// Workspace file written by `wb workspace` (`wb.workspaceFile`).
+    pub workspace_file: Option<String>,
 }
 
 impl WbConfig {
@@ -44,9 +103,114 @@ impl WbConfig {
             _ => NamingConvention::Flat,
         };
 
+        let list_format = git::run(&["config", "--get", "wb.listFormat"])
+            .ok()
+            .filter(|f| !f.is_empty());
+
+        let base = git::run(&["config", "--get", "wb.base"])
+            .ok()
+            .filter(|b| !b.is_empty());
+
+        let land_strategy = match git::run(&["config", "--get", "wb.landStrategy"]) {
+            Ok(val) => match val.as_str() {
+                "merge" => LandStrategy::Merge,
+                "rebase" => LandStrategy::Rebase,
+                _ => LandStrategy::Ff,
+            },
+            _ => LandStrategy::Ff,
+        };
+
+        // Multi-valued; each value may also hold several whitespace-separated globs
+        let copy_files = git::run(&["config", "--get-all", "wb.copyFiles"])
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let warm_dirs = match git::run(&["config", "--get-all", "wb.warmDirs"]) {
+            Ok(dirs) if !dirs.trim().is_empty() => {
+                dirs.split_whitespace().map(String::from).collect()
+            }
+            _ => ["target", "node_modules", ".venv"]
+                .map(String::from)
+                .to_vec(),
+        };
+
+        let env_file = git::run(&["config", "--get", "wb.envFile"])
+            .ok()
+            .filter(|f| !f.is_empty());
+
+        let port_base = git::run(&["config", "--get", "wb.portBase"])
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(3000);
+
+        let port_span = git::run(&["config", "--get", "wb.portSpan"])
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .filter(|&s| s > 0)
+            .unwrap_or(10);
+
+        let multiplexer = match git::run(&["config", "--get", "wb.multiplexer"]) {
+            Ok(val) if val == "zellij" => Multiplexer::Zellij,
+            Ok(val) if val == "tmux" => Multiplexer::Tmux,
+            _ if std::env::var_os("ZELLIJ").is_some() => Multiplexer::Zellij,
+            _ => Multiplexer::Tmux,
+        };
+
+        let session_windows = git::run(&["config", "--get-all", "wb.sessionWindows"])
+            .unwrap_or_default()
+            .lines()
+            .filter(|w| !w.trim().is_empty())
+            .map(String::from)
+            .collect();
+
+        let session_layout = git::run(&["config", "--get", "wb.sessionLayout"])
+            .ok()
+            .filter(|l| !l.is_empty());
+
+        let session_on_create = get_bool("wb.sessionOnCreate");
+        let session_on_delete = get_bool("wb.sessionOnDelete");
+
+        let editor = git::run(&["config", "--get", "wb.editor"])
+            .ok()
+            .filter(|e| !e.is_empty());
+
+        let workspace_file = git::run(&["config", "--get", "wb.workspaceFile"])
+            .ok()
+            .filter(|f| !f.is_empty());
+
         Ok(WbConfig {
             worktree_dir,
             naming,
+            list_format,
+            base,
+            land_strategy,
+            copy_files,
+            warm_dirs,
+            env_file,
+            port_base,
+            port_span,
+            multiplexer,
+            session_windows,
+            session_layout,
+            session_on_create,
+            session_on_delete,
+            editor,
+            workspace_file,
         })
     }
+
+    /// The base branch: `wb.base`, or the repository's default branch.
+    pub fn base_branch(&self) -> Result<String> {
+        match self.base {
+            Some(ref base) => Ok(base.clone()),
+            None => git::default_branch(),
+        }
+    }
+}
+
+/// Read a boolean git config key, treating unset or invalid values as false.
+fn get_bool(key: &str) -> bool {
+    git::run(&["config", "--type=bool", "--get", key]).is_ok_and(|v| v == "true")
 }
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::WbConfig;
+use crate::git;
+
+const BLOCK_START: &str = "# >>> wb >>>";
+const BLOCK_END: &str = "# <<< wb <<<";
+
+/// The per-worktree variables for `branch`, allocating its port range on first use.
+pub fn vars(config: &WbConfig, branch: &str, path: &Path) -> Result<Vec<(String, String)>> {
+    let port = port_for(config, branch)?;
+    let repo = git::find_root_dir()?
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(vec![
+        ("WB_BRANCH".to_string(), branch.to_string()),
+        ("WB_WORKTREE".to_string(), path.display().to_string()),
+        ("WB_PORT".to_string(), port.to_string()),
+        (
+            "WB_PORT_RANGE".to_string(),
+            format!("{}-{}", port, port.saturating_add(config.port_span - 1)),
+        ),
+        ("WB_DB_NAME".to_string(), db_name(&repo, branch)),
+    ])
+}
+
+/// Write the variables into the `wb.envFile` of the worktree at `path`, if one is configured.
+pub fn write_env_file(config: &WbConfig, branch: &str, path: &Path) -> Result<()> {
+    let Some(ref file) = config.env_file else {
+        return Ok(());
+    };
+    let vars = vars(config, branch, path)?;
+    let target = path.join(file);
+    let existing = fs::read_to_string(&target).unwrap_or_default();
+    // direnv evaluates .envrc as shell, so its variables must be exported
+    let export = file.ends_with(".envrc");
+    fs::write(&target, render_block(&existing, &vars, export))
+        .with_context(|| format!("cannot write '{}'", target.display()))?;
+    Ok(())
+}
+
+/// The branch's first port, stored in `branch.<name>.wbPort` so it survives
+/// restarts and moves with the branch on rename.
+fn port_for(config: &WbConfig, branch: &str) -> Result<u16> {
+    let key = format!("branch.{}.wbPort", branch);
+    if let Some(port) = git::run(&["config", "--get", &key])
+        .ok()
+        .and_then(|p| p.parse().ok())
+    {
+        return Ok(port);
+    }
+
+    let taken: HashSet<u16> = git::run(&["config", "--get-regexp", r"^branch\..*\.wbport$"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once(' ')?.1.parse().ok())
+        .collect();
+    let Some(port) = free_port(config.port_base, config.port_span, &taken) else {
+        bail!("fatal: no free port range left above {}", config.port_base);
+    };
+
+    git::run(&["config", &key, &port.to_string()])?;
+    Ok(port)
+}
+
+/// Forget a branch's port range, so the next use allocates a fresh one.
+pub fn release_port(branch: &str) {
+    let _ = git::run(&["config", "--unset", &format!("branch.{}.wbPort", branch)]);
+}
+
+/// The first range start at or above `base`, in steps of `span`, that is not taken.
+fn free_port(base: u16, span: u16, taken: &HashSet<u16>) -> Option<u16> {
+    let span = span.max(1);
+    (0..)
+        .map_while(|i: u32| {
+            let start = u32::from(base) + i * u32::from(span);
+            let end = start + u32::from(span) - 1;
+            (end <= u32::from(u16::MAX)).then_some(start as u16)
+        })
+        .find(|start| !taken.contains(start))
+}
+
+/// A database name for the branch: `<repo>_<branch>`, lowercased, with
+/// anything outside `[a-z0-9_]` replaced, and cut to Postgres' 63 bytes.
+fn db_name(repo: &str, branch: &str) -> String {
+    let mut name: String = format!("{}_{}", repo, branch)
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    name.truncate(63);
+    name
+}
+
+/// `KEY=value` lines, quoted where needed.
+pub fn format_vars(vars: &[(String, String)]) -> Vec<String> {
+    vars.iter()
+        .map(|(key, value)| format!("{}={}", key, quote(value)))
+        .collect()
+}
+
+/// Replace wb's marked block in `existing` (or append one) with `vars`,
+/// leaving the user's own lines untouched.
+fn render_block(existing: &str, vars: &[(String, String)], export: bool) -> String {
+    let prefix = if export { "export " } else { "" };
+    let mut block = vec![BLOCK_START.to_string()];
+    block.extend(
+        format_vars(vars)
+            .into_iter()
+            .map(|v| format!("{}{}", prefix, v)),
+    );
+    block.push(BLOCK_END.to_string());
+
+    let mut out = Vec::new();
+    let mut lines = existing.lines();
+    let mut replaced = false;
+    while let Some(line) = lines.next() {
+        if line == BLOCK_START && !replaced {
+            for line in lines.by_ref() {
+                if line == BLOCK_END {
+                    break;
+                }
+            }
+            out.append(&mut block.clone());
+            replaced = true;
+        } else {
+            out.push(line.to_string());
+        }
+    }
+    if !replaced {
+        out.append(&mut block);
+    }
+
+    out.join("\n") + "\n"
+}
+
+/// Quote a value if it contains anything a shell or dotenv parser could misread.
+fn quote(value: &str) -> String {
+    if value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./:@".contains(c))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_port() {
+        let taken = HashSet::from([3000, 3020]);
+        assert_eq!(free_port(3000, 10, &HashSet::new()), Some(3000));
+        assert_eq!(free_port(3000, 10, &taken), Some(3010));
+        assert_eq!(free_port(65530, 10, &HashSet::new()), None);
+    }
+
+    #[test]
+    fn test_db_name() {
+        assert_eq!(db_name("my-app", "feature/Auth"), "my_app_feature_auth");
+        assert_eq!(db_name("a", &"b".repeat(100)).len(), 63);
+    }
+
+    #[test]
+    fn test_render_block() {
+        let vars = vec![
+            ("WB_BRANCH".to_string(), "feat".to_string()),
+            ("WB_WORKTREE".to_string(), "/tmp/my repo".to_string()),
+        ];
+        let fresh = render_block("SECRET=1", &vars, false);
+        assert_eq!(
+            fresh,
+            "SECRET=1\n# >>> wb >>>\nWB_BRANCH=feat\nWB_WORKTREE='/tmp/my repo'\n# <<< wb <<<\n"
+        );
+
+        let updated = render_block(
+            "# >>> wb >>>\nWB_BRANCH=old\n# <<< wb <<<\nOTHER=2\n",
+            &vars[..1],
+            true,
+        );
+        assert_eq!(
+            updated,
+            "# >>> wb >>>\nexport WB_BRANCH=feat\n# <<< wb <<<\nOTHER=2\n"
+        );
+    }
+}
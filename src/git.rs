@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -56,13 +57,64 @@ pub fn find_root_dir() -> Result<PathBuf> {
 #[derive(Debug, Clone)]
 pub struct BranchInfo {
     pub name: String,
+    pub sha: String,
+    pub short_sha: String,
     pub is_head: bool,
     pub is_remote: bool,
+    /// Upstream branch (e.g. `origin/main`), if configured.
+    pub upstream: Option<String>,
+    pub tracking: Tracking,
+    /// Subject line of the tip commit.
+    pub subject: String,
+    /// Relative committer date of the tip commit (e.g. `2 days ago`).
+    pub date: String,
+    /// For remote branches: the local branch tracking it or sharing its name.
+    pub local_branch: Option<String>,
+}
+
+impl BranchInfo {
+    /// For remote branches, the branch name without the remote (`origin/a/b` → `a/b`).
+    pub fn remote_branch_name(&self) -> Option<&str> {
+        if !self.is_remote {
+            return None;
+        }
+        self.name.split_once('/').map(|(_, name)| name)
+    }
+}
+
+/// Relationship between a branch and its upstream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tracking {
+    pub ahead: u32,
+    pub behind: u32,
+    /// The upstream is configured but its ref no longer exists.
+    pub gone: bool,
+}
+
+impl Tracking {
+    /// Parse `%(upstream:track,nobracket)` output, e.g. `ahead 1, behind 2` or `gone`.
+    pub fn parse(track: &str) -> Self {
+        let mut tracking = Tracking::default();
+        for part in track.split(',').map(str::trim) {
+            if part == "gone" {
+                tracking.gone = true;
+            } else if let Some(n) = part.strip_prefix("ahead ") {
+                tracking.ahead = n.parse().unwrap_or(0);
+            } else if let Some(n) = part.strip_prefix("behind ") {
+                tracking.behind = n.parse().unwrap_or(0);
+            }
+        }
+        tracking
+    }
 }
 
 /// List branches using git for-each-ref.
 pub fn list_branches(filter: BranchFilter) -> Result<Vec<BranchInfo>> {
-    let format = "%(refname:short)\t%(HEAD)";
+    // Subject goes last: it is free-form and may be empty.
+    let format = "%(refname)\t%(refname:short)\t%(symref)\t%(HEAD)\t\
+                  %(objectname)\t%(objectname:short)\t\
+                  %(upstream:short)\t%(upstream:track,nobracket)\t\
+                  %(committerdate:relative)\t%(committeremail)\t%(contents:subject)";
 
     let mut args = vec!["for-each-ref", "--format", format];
 
@@ -96,7 +148,12 @@ pub fn list_branches(filter: BranchFilter) -> Result<Vec<BranchInfo>> {
         args.push(&no_contains_arg);
     }
 
-    args.push("refs/heads/");
+    if filter.scope != BranchScope::Remote {
+        args.push("refs/heads/");
+    }
+    if filter.scope != BranchScope::Local {
+        args.push("refs/remotes/");
+    }
 
     let output = run(&args)?;
     let mut branches = Vec::new();
@@ -105,34 +162,177 @@ pub fn list_branches(filter: BranchFilter) -> Result<Vec<BranchInfo>> {
         if line.is_empty() {
             continue;
         }
-        let parts: Vec<&str> = line.splitn(2, '\t').collect();
-        if parts.len() < 2 {
+        let parts: Vec<&str> = line.splitn(11, '\t').collect();
+        if parts.len() < 5 {
             continue;
         }
+        let field = |i: usize| parts.get(i).copied().unwrap_or("");
+
+        // Skip symbolic refs such as `origin/HEAD`
+        if !field(2).is_empty() {
+            continue;
+        }
+
+        if let Some(ref committer) = filter.committer {
+            let email = field(9).trim_start_matches('<').trim_end_matches('>');
+            if !email.eq_ignore_ascii_case(committer) {
+                continue;
+            }
+        }
 
         branches.push(BranchInfo {
-            name: parts[0].to_string(),
-            is_head: parts[1].trim() == "*",
-            is_remote: false,
+            name: field(1).to_string(),
+            sha: field(4).to_string(),
+            short_sha: field(5).to_string(),
+            is_head: field(3).trim() == "*",
+            is_remote: field(0).starts_with("refs/remotes/"),
+            upstream: Some(field(6)).filter(|u| !u.is_empty()).map(String::from),
+            tracking: Tracking::parse(field(7)),
+            subject: field(10).to_string(),
+            date: field(8).to_string(),
+            local_branch: None,
         });
     }
 
-    // Apply glob pattern filter
-    if let Some(ref pattern) = filter.pattern {
-        branches.retain(|b| glob_match::glob_match(pattern, &b.name));
+    // Apply glob pattern filter (a branch is kept if it matches any pattern)
+    if !filter.patterns.is_empty() {
+        branches.retain(|b| {
+            filter
+                .patterns
+                .iter()
+                .any(|pattern| glob_match::glob_match(pattern, &b.name))
+        });
+    }
+
+    // Checked last: it runs several git commands per branch
+    if let Some(ref base) = filter.landed {
+        branches.retain(|b| !b.is_remote && b.name != *base && is_landed(&b.name, base));
     }
 
     Ok(branches)
 }
 
+/// Which refs `list_branches` scans.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BranchScope {
+    /// `refs/heads/`
+    #[default]
+    Local,
+    /// `refs/remotes/`
+    Remote,
+    /// Both local and remote-tracking branches.
+    All,
+}
+
 #[derive(Debug, Default)]
 pub struct BranchFilter {
+    pub scope: BranchScope,
     pub sort: Option<String>,
     pub merged: Option<String>,
     pub no_merged: Option<String>,
     pub contains: Option<String>,
     pub no_contains: Option<String>,
-    pub pattern: Option<String>,
+    pub patterns: Vec<String>,
+    /// Only keep branches whose tip commit has this committer email.
+    pub committer: Option<String>,
+    /// Only keep branches whose changes have landed in this base, even via
+    /// squash or rebase merges (see `is_landed`).
+    pub landed: Option<String>,
+}
+
+/// Link remote branches to their local counterparts: a local branch whose
+/// upstream is the remote branch, or failing that, one with the same name.
+pub fn link_local_branches(branches: &mut [BranchInfo]) -> Result<()> {
+    let output = run(&[
+        "for-each-ref",
+        "--format",
+        "%(refname:short)\t%(upstream:short)",
+        "refs/heads/",
+    ])?;
+    // `run` trims output, so the last line may have lost its empty upstream field
+    let locals: Vec<(&str, &str)> = output
+        .lines()
+        .map(|line| line.split_once('\t').unwrap_or((line, "")))
+        .collect();
+
+    for branch in branches.iter_mut().filter(|b| b.is_remote) {
+        let short = branch.remote_branch_name().unwrap_or(&branch.name);
+        branch.local_branch = locals
+            .iter()
+            .find(|(_, upstream)| *upstream == branch.name)
+            .or_else(|| locals.iter().find(|(name, _)| *name == short))
+            .map(|(name, _)| name.to_string());
+    }
+
+    Ok(())
+}
+
+/// Find the single remote-tracking branch named `<remote>/<name>`, if exactly one remote has it.
+pub fn find_unique_remote_branch(name: &str) -> Result<Option<String>> {
+    let output = run(&[
+        "for-each-ref",
+        "--format",
+        "%(refname:short)",
+        "refs/remotes/",
+    ])?;
+    let mut matches = output
+        .lines()
+        .filter(|r| r.split_once('/').map(|(_, n)| n) == Some(name));
+
+    match (matches.next(), matches.next()) {
+        (Some(remote), None) => Ok(Some(remote.to_string())),
+        _ => Ok(None),
+    }
+}
+
+/// Count stash entries per branch, from the `WIP on <branch>:` / `On <branch>:`
+/// messages git records (the stash itself is shared by all worktrees).
+pub fn stash_counts() -> Result<HashMap<String, usize>> {
+    let output = run(&["stash", "list", "--format=%gs"])?;
+    let mut counts = HashMap::new();
+
+    for line in output.lines() {
+        let rest = line
+            .strip_prefix("WIP on ")
+            .or_else(|| line.strip_prefix("On "));
+        if let Some((branch, _)) = rest.and_then(|r| r.split_once(':')) {
+            *counts.entry(branch.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Determine the repository's default branch: the bare repo's `HEAD`,
+/// else `origin/HEAD`, else `main` or `master` if they exist.
+pub fn default_branch() -> Result<String> {
+    let git_dir = find_git_dir()?;
+    if let Ok(head) = run_in(&git_dir, &["symbolic-ref", "--short", "HEAD"]) {
+        if branch_exists(&head) {
+            return Ok(head);
+        }
+    }
+
+    if let Ok(remote_head) = run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        if let Some(name) = remote_head.strip_prefix("origin/") {
+            if branch_exists(name) {
+                return Ok(name.to_string());
+            }
+        }
+    }
+
+    for name in ["main", "master"] {
+        if branch_exists(name) {
+            return Ok(name.to_string());
+        }
+    }
+
+    bail!("fatal: cannot determine the default branch; set it with 'git config wb.base <branch>'")
+}
+
+/// Get the configured `user.email`.
+pub fn user_email() -> Result<String> {
+    run(&["config", "--get", "user.email"]).context("user.email is not configured")
 }
 
 /// Check if a branch exists.
@@ -140,6 +340,12 @@ pub fn branch_exists(name: &str) -> bool {
     run(&["rev-parse", "--verify", &format!("refs/heads/{}", name)]).is_ok()
 }
 
+/// Names of all local branches.
+pub fn local_branch_names() -> Result<Vec<String>> {
+    let out = run(&["for-each-ref", "--format=%(refname:lstrip=2)", "refs/heads"])?;
+    Ok(out.lines().map(str::to_string).collect())
+}
+
 /// Create a branch ref (without worktree).
 #[allow(dead_code)]
 pub fn create_branch(name: &str, start_point: Option<&str>) -> Result<()> {
@@ -151,6 +357,83 @@ pub fn create_branch(name: &str, start_point: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Check whether a branch is merged in the sense `git branch -d` uses:
+/// into its upstream if it has one, otherwise into `HEAD`.
+pub fn is_merged(name: &str) -> bool {
+    let upstream = format!("refs/heads/{}@{{upstream}}", name);
+    let base = if run(&["rev-parse", "--verify", "--quiet", &upstream]).is_ok() {
+        upstream
+    } else {
+        "HEAD".to_string()
+    };
+    run(&[
+        "merge-base",
+        "--is-ancestor",
+        &format!("refs/heads/{}", name),
+        &base,
+    ])
+    .is_ok()
+}
+
+/// Check whether a branch's changes are already in `base`, including
+/// branches that were rebase- or squash-merged (and so are not ancestors).
+///
+/// Rebase merges are found with `git cherry` (every commit has a
+/// patch-equivalent in `base`). Squash merges are found by squashing the
+/// branch onto its merge base with `commit-tree` and checking that single
+/// patch against `base` the same way. That probe writes a commit object;
+/// its fixed identity and date keep it the same object on every call.
+pub fn is_landed(name: &str, base: &str) -> bool {
+    let branch = format!("refs/heads/{}", name);
+
+    if run(&["merge-base", "--is-ancestor", &branch, base]).is_ok() {
+        return true;
+    }
+
+    let tree = |rev: &str| run(&["rev-parse", &format!("{}^{{tree}}", rev)]).ok();
+    if tree(&branch).is_some() && tree(&branch) == tree(base) {
+        return true;
+    }
+
+    let all_upstream = |head: &str| {
+        run(&["cherry", base, head])
+            .is_ok_and(|out| !out.is_empty() && out.lines().all(|l| l.starts_with('-')))
+    };
+    if all_upstream(&branch) {
+        return true;
+    }
+
+    let Ok(merge_base) = run(&["merge-base", base, &branch]) else {
+        return false;
+    };
+    let Some(branch_tree) = tree(&branch) else {
+        return false;
+    };
+    // A fixed identity also works where user.name and user.email are unset
+    let squashed = Command::new("git")
+        .args([
+            "commit-tree",
+            &branch_tree,
+            "-p",
+            &merge_base,
+            "-m",
+            "wb: squash check",
+        ])
+        .env("GIT_AUTHOR_NAME", "wb")
+        .env("GIT_AUTHOR_EMAIL", "wb@localhost")
+        .env("GIT_AUTHOR_DATE", "@0 +0000")
+        .env("GIT_COMMITTER_NAME", "wb")
+        .env("GIT_COMMITTER_EMAIL", "wb@localhost")
+        .env("GIT_COMMITTER_DATE", "@0 +0000")
+        .output();
+    match squashed {
+        Ok(out) if out.status.success() => {
+            all_upstream(String::from_utf8_lossy(&out.stdout).trim())
+        }
+        _ => false,
+    }
+}
+
 /// Delete a branch ref.
 pub fn delete_branch(name: &str, force: bool) -> Result<()> {
     let flag = if force { "-D" } else { "-d" };
@@ -172,8 +455,89 @@ pub fn copy_branch(old: &str, new: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Get the stack parent recorded for a branch (`branch.<name>.wbParent`).
+pub fn stack_parent(name: &str) -> Option<String> {
+    run(&["config", "--get", &format!("branch.{}.wbParent", name)])
+        .ok()
+        .filter(|p| !p.is_empty())
+}
+
+/// Get every recorded stack parent, keyed by child branch.
+pub fn stack_parents() -> HashMap<String, String> {
+    let output = run(&["config", "--get-regexp", r"^branch\..*\.wbparent$"]).unwrap_or_default();
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, parent) = line.split_once(' ')?;
+            let child = key.strip_prefix("branch.")?.strip_suffix(".wbparent")?;
+            Some((child.to_string(), parent.to_string()))
+        })
+        .collect()
+}
+
+/// Record `parent` as the stack parent of `name`, along with the parent's
+/// current commit, so a restack only replays the branch's own commits.
+pub fn set_stack_parent(name: &str, parent: &str) -> Result<()> {
+    run(&["config", &format!("branch.{}.wbParent", name), parent])?;
+    let parent_sha = run(&["rev-parse", &format!("refs/heads/{}", parent)])?;
+    set_stack_base(name, &parent_sha)
+}
+
+/// Point a branch at a new stack parent, keeping its recorded base commit.
+pub fn reparent(name: &str, parent: Option<&str>) -> Result<()> {
+    let key = format!("branch.{}.wbParent", name);
+    match parent {
+        Some(parent) => {
+            run(&["config", &key, parent])?;
+        }
+        None => {
+            let _ = run(&["config", "--unset", &key]);
+            let _ = run(&["config", "--unset", &format!("branch.{}.wbBase", name)]);
+        }
+    }
+    Ok(())
+}
+
+/// Get the parent commit a branch was last stacked on (`branch.<name>.wbBase`).
+pub fn stack_base(name: &str) -> Option<String> {
+    run(&["config", "--get", &format!("branch.{}.wbBase", name)])
+        .ok()
+        .filter(|b| !b.is_empty())
+}
+
+/// Record the parent commit a branch is stacked on.
+pub fn set_stack_base(name: &str, sha: &str) -> Result<()> {
+    run(&["config", &format!("branch.{}.wbBase", name), sha])?;
+    Ok(())
+}
+
+/// Check whether `ancestor` is an ancestor of (or equal to) `rev`.
+pub fn is_ancestor(ancestor: &str, rev: &str) -> bool {
+    run(&["merge-base", "--is-ancestor", ancestor, rev]).is_ok()
+}
+
 /// Get the current branch name from HEAD.
 #[allow(dead_code)]
 pub fn current_branch() -> Result<String> {
     run(&["symbolic-ref", "--short", "HEAD"])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tracking() {
+        assert_eq!(Tracking::parse(""), Tracking::default());
+        assert_eq!(
+            Tracking::parse("ahead 3, behind 12"),
+            Tracking {
+                ahead: 3,
+                behind: 12,
+                gone: false
+            }
+        );
+        assert_eq!(Tracking::parse("behind 1").behind, 1);
+        assert!(Tracking::parse("gone").gone);
+    }
+}
@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::git;
+
+/// How many worktrees the history remembers.
+const MAX_ENTRIES: usize = 50;
+
+/// Remember a switch to the worktree at `target`: the worktree being left
+/// (if it is in the same repository) and then `target` move to the front.
+pub fn record(target: &Path) {
+    let Some(target) = worktree_root(target) else {
+        return;
+    };
+    let Some(file) = history_file(&target) else {
+        return;
+    };
+    let source = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| worktree_root(&cwd))
+        .filter(|src| history_file(src).as_ref() == Some(&file));
+
+    let mut entries = read(&file);
+    for path in source.into_iter().chain([target]) {
+        entries.retain(|e| *e != path);
+        entries.insert(0, path);
+    }
+    entries.truncate(MAX_ENTRIES);
+
+    let contents: String = entries
+        .iter()
+        .map(|e| format!("{}\n", e.display()))
+        .collect();
+    let _ = fs::write(&file, contents);
+}
+
+/// Worktrees of the current repository, most recent first, leaving out the
+/// one wb runs in and any that no longer exist.
+pub fn recent() -> Vec<PathBuf> {
+    let Ok(cwd) = std::env::current_dir() else {
+        return Vec::new();
+    };
+    let current = worktree_root(&cwd);
+    let Some(file) = history_file(&cwd) else {
+        return Vec::new();
+    };
+    read(&file)
+        .into_iter()
+        .filter(|e| Some(e) != current.as_ref() && e.is_dir())
+        .collect()
+}
+
+fn read(file: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(file)
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// `<git-common-dir>/wb-history` of the repository `dir` belongs to.
+fn history_file(dir: &Path) -> Option<PathBuf> {
+    let common = git::run_in(dir, &["rev-parse", "--git-common-dir"]).ok()?;
+    let common = dir.join(common).canonicalize().ok()?;
+    Some(common.join("wb-history"))
+}
+
+/// The canonical top of the worktree containing `dir`.
+fn worktree_root(dir: &Path) -> Option<PathBuf> {
+    let top = git::run_in(dir, &["rev-parse", "--show-toplevel"]).ok()?;
+    PathBuf::from(top).canonicalize().ok()
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::git;
+
+/// A point in a branch's lifecycle where user commands can run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hook {
+    PostCreate,
+    PreDelete,
+    PostRename,
+    PostCopy,
+}
+
+impl Hook {
+    /// Name used in the `wb.hook.<name>` config key.
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PostCreate => "postCreate",
+            Hook::PreDelete => "preDelete",
+            Hook::PostRename => "postRename",
+            Hook::PostCopy => "postCopy",
+        }
+    }
+
+    /// File name of the script under `.bare/wb-hooks/`.
+    fn script_name(self) -> &'static str {
+        match self {
+            Hook::PostCreate => "post-create",
+            Hook::PreDelete => "pre-delete",
+            Hook::PostRename => "post-rename",
+            Hook::PostCopy => "post-copy",
+        }
+    }
+}
+
+/// The branch a hook runs for, passed to it as `WB_*` environment variables.
+#[derive(Debug, Default)]
+pub struct HookContext<'a> {
+    pub branch: &'a str,
+    pub path: Option<&'a Path>,
+    pub old_branch: Option<&'a str>,
+    pub old_path: Option<&'a Path>,
+}
+
+/// Run every `wb.hook.<name>` command, then the `.bare/wb-hooks/<name>` script,
+/// stopping at the first one that fails.
+///
+/// Hooks run in the worktree (or the current directory if there is none), with
+/// their stdout sent to stderr so it cannot be mistaken for a wb directive.
+pub fn run(hook: Hook, ctx: &HookContext) -> Result<()> {
+    let key = format!("wb.hook.{}", hook.name());
+    let commands = git::run(&["config", "--get-all", &key]).unwrap_or_default();
+    let script = hook_script(hook);
+
+    let mut invocations: Vec<(String, Command)> = commands
+        .lines()
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(c);
+            (c.to_string(), cmd)
+        })
+        .collect();
+    if let Some(script) = script {
+        invocations.push((script.display().to_string(), Command::new(&script)));
+    }
+    if invocations.is_empty() {
+        return Ok(());
+    }
+
+    for (label, mut cmd) in invocations {
+        if let Some(path) = ctx.path.filter(|p| p.is_dir()) {
+            cmd.current_dir(path);
+        }
+        cmd.env("WB_HOOK", hook.name())
+            .env("WB_BRANCH", ctx.branch)
+            .stdout(std::io::stderr());
+        set_or_remove(&mut cmd, "WB_WORKTREE", ctx.path.map(Path::as_os_str));
+        set_or_remove(
+            &mut cmd,
+            "WB_OLD_BRANCH",
+            ctx.old_branch.map(|b| b.as_ref()),
+        );
+        set_or_remove(
+            &mut cmd,
+            "WB_OLD_WORKTREE",
+            ctx.old_path.map(Path::as_os_str),
+        );
+
+        let status = cmd
+            .status()
+            .with_context(|| format!("failed to run {} hook '{}'", hook.name(), label))?;
+        if !status.success() {
+            match status.code() {
+                Some(code) => bail!(
+                    "fatal: {} hook '{}' failed with exit code {}",
+                    hook.name(),
+                    label,
+                    code
+                ),
+                None => bail!("fatal: {} hook '{}' was killed", hook.name(), label),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a hook after its operation has already happened; a failure is only reported.
+pub fn run_post(hook: Hook, ctx: &HookContext) {
+    if let Err(e) = run(hook, ctx) {
+        let msg = e.to_string();
+        eprintln!("warning: {}", msg.strip_prefix("fatal: ").unwrap_or(&msg));
+    }
+}
+
+/// The executable `.bare/wb-hooks/<name>` script, if there is one.
+fn hook_script(hook: Hook) -> Option<PathBuf> {
+    let path = git::find_git_dir()
+        .ok()?
+        .join("wb-hooks")
+        .join(hook.script_name());
+    let path = path.canonicalize().ok()?;
+    is_executable(&path).then_some(path)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn set_or_remove(cmd: &mut Command, key: &str, value: Option<&std::ffi::OsStr>) {
+    match value {
+        Some(value) => cmd.env(key, value),
+        None => cmd.env_remove(key),
+    };
+}
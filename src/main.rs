@@ -1,19 +1,33 @@
 mod cli;
 mod commands;
 mod config;
+mod environment;
 mod error;
 mod git;
+mod history;
+mod hooks;
 mod output;
+mod populate;
 mod resolve;
+mod session;
 mod shell;
+mod template;
+mod terminal;
+mod ui;
+mod workspace;
 mod worktree;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{CommandFactory, Parser};
 
-use cli::{Cli, Command};
+use cli::{Cli, Command, FilterArgs};
+use config::WbConfig;
+use git::{BranchFilter, BranchScope};
+use output::ListStyle;
+use template::Template;
 
 fn main() {
+    shell::take_directive_file();
     if let Err(e) = run() {
         eprintln!("{}", e);
         std::process::exit(1);
@@ -24,28 +38,229 @@ fn run() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        None => {
-            Cli::command().print_help()?;
-            Ok(())
-        }
+        None => run_branch_flags(&cli),
 
-        Some(Command::Init { target, directory }) => match target.as_deref() {
-            Some("zsh") | Some("bash") | Some("fish") => {
-                shell::output_shell_init(target.as_deref().unwrap())
+        Some(Command::Init {
+            target,
+            directory,
+            cmd,
+            bind,
+        }) => match target.as_deref() {
+            Some(shell @ ("zsh" | "bash" | "fish")) => {
+                shell::output_shell_init(shell, cmd.as_deref().unwrap_or("wb"), bind.as_deref())
+            }
+            _ => {
+                if cmd.is_some() || bind.is_some() {
+                    bail!("fatal: --cmd and --bind only apply to shell integration");
+                }
+                commands::init_repo::run(target.as_deref(), directory.as_deref())
             }
-            _ => commands::init_repo::run(target.as_deref(), directory.as_deref()),
         },
 
-        Some(Command::List) => commands::list::run(),
+        Some(Command::List {
+            verbose,
+            json,
+            porcelain,
+            format,
+            stack,
+            worktrees,
+            filter,
+            patterns,
+        }) => {
+            let style = if stack {
+                ListStyle::Stack
+            } else if let Some(format) = format {
+                ListStyle::Template(Template::parse(&format)?)
+            } else if json {
+                ListStyle::Json
+            } else if porcelain {
+                ListStyle::Porcelain
+            } else if verbose > 0 {
+                ListStyle::Verbose(verbose)
+            } else {
+                ListStyle::Human
+            };
+            if worktrees {
+                if filter.is_set() {
+                    bail!("fatal: branch filters cannot be used with --worktrees");
+                }
+                return commands::list::run_worktrees(style);
+            }
+            commands::list::run(branch_filter(&filter, patterns)?, style)
+        }
+
+        Some(Command::Status) => commands::status::run(),
+
+        Some(Command::Sync) => commands::sync::run(),
+
+        Some(Command::Clean {
+            force,
+            dry_run,
+            yes,
+            no_fetch,
+        }) => commands::clean::run(force, dry_run, yes, !no_fetch),
+
+        Some(Command::Land {
+            branch,
+            base,
+            strategy,
+        }) => commands::land::run(branch.as_deref(), base.as_deref(), strategy),
+
+        Some(Command::Restack) => commands::restack::run(),
+
+        Some(Command::Session { branch }) => commands::session::run(branch.as_deref()),
 
-        Some(Command::Create { branch, from }) => commands::create::run(&branch, from.as_deref()),
+        Some(Command::Open { branch }) => commands::open::run(branch.as_deref()),
+
+        Some(Command::Workspace) => commands::workspace::run(),
+
+        Some(Command::Ui) => commands::ui::run(),
+
+        Some(Command::Back) => commands::back::run(),
+
+        Some(Command::Recent { index, list }) => commands::recent::run(index, list),
+
+        Some(Command::Env { branch, write }) => commands::env::run(branch.as_deref(), write),
+
+        Some(Command::Foreach {
+            pattern,
+            jobs,
+            keep_going,
+            command,
+        }) => commands::foreach::run(&command, pattern.as_deref(), jobs, keep_going),
+
+        Some(Command::Create { branch, from, warm }) => commands::create::run(
+            &branch,
+            from.as_deref(),
+            warm.as_ref().map(|w| w.as_deref()),
+        ),
 
         Some(Command::Delete { branches, force }) => commands::delete::run(&branches, force),
 
-        Some(Command::Rename { new_name, old_name }) => {
-            commands::rename::run(&new_name, old_name.as_deref())
+        Some(Command::Rename {
+            new_name,
+            old_name,
+            force,
+        }) => commands::rename::run(&new_name, old_name.as_deref(), force),
+
+        Some(Command::Copy {
+            new_name,
+            from,
+            force,
+        }) => commands::copy::run(&new_name, from.as_deref(), force),
+    }
+}
+
+/// Dispatch the git-branch-style interface: `wb [-d|-D|-m|-M|-c|-C|-l|--show-current] [<args>...]`.
+fn run_branch_flags(cli: &Cli) -> Result<()> {
+    let args = &cli.args;
+    let listing = cli.list || cli.verbose > 0 || cli.filter.is_set();
+
+    if cli.filter.is_set() && mode_flag_given(cli) {
+        bail!("fatal: branch filters can only be used when listing branches");
+    }
+    if cli.verbose > 0 && mode_flag_given(cli) {
+        bail!("fatal: --verbose can only be used when listing branches");
+    }
+
+    if cli.delete || cli.force_delete {
+        if args.is_empty() {
+            bail!("fatal: branch name required");
+        }
+        return commands::delete::run(args, cli.force_delete);
+    }
+
+    if cli.rename || cli.force_rename {
+        return match args.as_slice() {
+            [new] => commands::rename::run(new, None, cli.force_rename),
+            [old, new] => commands::rename::run(new, Some(old), cli.force_rename),
+            [] => bail!("fatal: branch name required"),
+            _ => bail!("fatal: too many arguments for a rename operation"),
+        };
+    }
+
+    if cli.copy || cli.force_copy {
+        return match args.as_slice() {
+            [new] => commands::copy::run(new, None, cli.force_copy),
+            [old, new] => commands::copy::run(new, Some(old), cli.force_copy),
+            [] => bail!("fatal: branch name required"),
+            _ => bail!("fatal: too many arguments for a copy operation"),
+        };
+    }
+
+    if cli.show_current {
+        if !args.is_empty() {
+            bail!("fatal: too many arguments to show current branch");
         }
+        return commands::show_current::run();
+    }
 
-        Some(Command::Copy { new_name, from }) => commands::copy::run(&new_name, from.as_deref()),
+    // Like `git branch -v` or `git branch --merged`, these imply listing
+    if listing {
+        let filter = branch_filter(&cli.filter, args.clone())?;
+        let style = if cli.verbose > 0 {
+            ListStyle::Verbose(cli.verbose)
+        } else {
+            ListStyle::Human
+        };
+        return commands::list::run(filter, style);
     }
+
+    match args.as_slice() {
+        [] => {
+            Cli::command().print_help()?;
+            Ok(())
+        }
+        // Like `cd -`
+        [name] if name == "-" => commands::back::run(),
+        [name] => commands::create::run(name, None, None),
+        [name, start_point] => commands::create::run(name, Some(start_point), None),
+        _ => bail!("fatal: too many arguments to create a branch"),
+    }
+}
+
+/// Whether a non-listing mode flag (`-d`, `-m`, `-c`, ...) was given.
+fn mode_flag_given(cli: &Cli) -> bool {
+    cli.delete
+        || cli.force_delete
+        || cli.rename
+        || cli.force_rename
+        || cli.copy
+        || cli.force_copy
+        || cli.show_current
+}
+
+/// Build a `BranchFilter` from CLI filter flags and glob patterns.
+fn branch_filter(args: &FilterArgs, patterns: Vec<String>) -> Result<BranchFilter> {
+    let committer = if args.mine {
+        Some(git::user_email()?)
+    } else {
+        None
+    };
+
+    let landed = match args.landed {
+        Some(Some(ref base)) => Some(base.clone()),
+        Some(None) => Some(WbConfig::load()?.base_branch()?),
+        None => None,
+    };
+
+    let scope = if args.all {
+        BranchScope::All
+    } else if args.remotes {
+        BranchScope::Remote
+    } else {
+        BranchScope::Local
+    };
+
+    Ok(BranchFilter {
+        scope,
+        sort: args.sort.clone(),
+        merged: args.merged.clone(),
+        no_merged: args.no_merged.clone(),
+        contains: args.contains.clone(),
+        no_contains: args.no_contains.clone(),
+        patterns,
+        committer,
+        landed,
+    })
 }
@@ -1,7 +1,30 @@
-use colored::Colorize;
+use colored::{Color, Colorize};
+use serde::Serialize;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use crate::git::BranchInfo;
-use crate::worktree::WorktreeInfo;
+use crate::template::{Placeholder, Template};
+use crate::worktree::{self, WorktreeInfo, WorktreeStatus};
+
+/// Output style for `wb list`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ListStyle {
+    /// Colored, `git branch`-like output.
+    #[default]
+    Human,
+    /// `git branch -v`-like output; the level is the number of `-v` flags.
+    Verbose(u8),
+    /// A JSON array of branch objects.
+    Json,
+    /// Stable tab-separated lines for scripts.
+    Porcelain,
+    /// One line per branch rendered from a user template (`--format`).
+    Template(Template),
+    /// Branches drawn as a tree of stacks (`--stack`).
+    Stack,
+}
 
 /// Format branch listing output, similar to `git branch` output.
 pub fn format_branch_list(branches: &[BranchInfo], worktrees: &[WorktreeInfo]) -> String {
@@ -16,12 +39,23 @@ pub fn format_branch_list(branches: &[BranchInfo], worktrees: &[WorktreeInfo]) -
 }
 
 fn format_branch_line(branch: &BranchInfo, worktrees: &[WorktreeInfo]) -> String {
-    let prefix = if branch.is_head {
+    format!(
+        "{}{}",
+        format_head_marker(branch),
+        format_branch_label(branch, worktrees)
+    )
+}
+
+fn format_head_marker(branch: &BranchInfo) -> String {
+    if branch.is_head {
         "* ".green().to_string()
     } else {
         "  ".to_string()
-    };
+    }
+}
 
+/// The branch name with its worktree indicator and remote note.
+fn format_branch_label(branch: &BranchInfo, worktrees: &[WorktreeInfo]) -> String {
     let name = if branch.is_head {
         branch.name.green().to_string()
     } else if branch.is_remote {
@@ -31,9 +65,7 @@ fn format_branch_line(branch: &BranchInfo, worktrees: &[WorktreeInfo]) -> String
     };
 
     // Check if branch has a worktree
-    let has_worktree = worktrees
-        .iter()
-        .any(|wt| wt.branch.as_deref() == Some(&branch.name) && !wt.is_bare);
+    let has_worktree = find_worktree(branch, worktrees).is_some();
 
     let worktree_indicator = if has_worktree && !branch.is_head {
         " +".cyan().to_string()
@@ -41,5 +73,538 @@ fn format_branch_line(branch: &BranchInfo, worktrees: &[WorktreeInfo]) -> String
         String::new()
     };
 
-    format!("{}{}{}", prefix, name, worktree_indicator)
+    format!(
+        "{}{}{}",
+        name,
+        worktree_indicator,
+        format_remote_note(branch)
+    )
+}
+
+/// For remote branches: the local counterpart, or how to materialize one.
+fn format_remote_note(branch: &BranchInfo) -> String {
+    let Some(short) = branch.remote_branch_name() else {
+        return String::new();
+    };
+    match branch.local_branch {
+        Some(ref local) => format!(" (local: {})", local).cyan().to_string(),
+        None => format!(" (wb create {})", short).dimmed().to_string(),
+    }
+}
+
+/// Format branches as stacks: each branch drawn under its recorded parent.
+/// Branches whose parent is not listed start a tree of their own.
+pub fn format_branch_stack(
+    branches: &[BranchInfo],
+    parents: &HashMap<String, String>,
+    worktrees: &[WorktreeInfo],
+) -> String {
+    let listed: HashSet<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+    let parent_of = |b: &BranchInfo| {
+        parents
+            .get(&b.name)
+            .map(String::as_str)
+            .filter(|p| listed.contains(p) && *p != b.name)
+    };
+
+    let mut lines = Vec::new();
+    let mut seen = HashSet::new();
+    for root in branches.iter().filter(|b| parent_of(b).is_none()) {
+        push_stack_lines(
+            root, "", "", branches, &parent_of, worktrees, &mut seen, &mut lines,
+        );
+    }
+    // Anything left over is part of a parent cycle; show it flat
+    for branch in branches {
+        if !seen.contains(branch.name.as_str()) {
+            lines.push(format_branch_line(branch, worktrees));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_stack_lines<'a>(
+    branch: &'a BranchInfo,
+    branch_prefix: &str,
+    child_prefix: &str,
+    branches: &'a [BranchInfo],
+    parent_of: &dyn Fn(&BranchInfo) -> Option<&'a str>,
+    worktrees: &[WorktreeInfo],
+    seen: &mut HashSet<&'a str>,
+    lines: &mut Vec<String>,
+) {
+    if !seen.insert(branch.name.as_str()) {
+        return;
+    }
+    lines.push(format!(
+        "{}{}{}",
+        format_head_marker(branch),
+        branch_prefix.dimmed(),
+        format_branch_label(branch, worktrees)
+    ));
+
+    let children: Vec<&BranchInfo> = branches
+        .iter()
+        .filter(|b| parent_of(b) == Some(branch.name.as_str()))
+        .collect();
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let (elbow, bar) = if last {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        push_stack_lines(
+            child,
+            &format!("{}{}", child_prefix, elbow),
+            &format!("{}{}", child_prefix, bar),
+            branches,
+            parent_of,
+            worktrees,
+            seen,
+            lines,
+        );
+    }
+}
+
+/// Format a verbose branch listing, similar to `git branch -v` / `-vv`.
+///
+/// Level 1 shows the short sha, ahead/behind counts and the tip commit's
+/// subject and date; level 2 also shows the upstream name and worktree path.
+pub fn format_branch_verbose(
+    branches: &[BranchInfo],
+    worktrees: &[WorktreeInfo],
+    level: u8,
+) -> String {
+    let width = branches.iter().map(|b| b.name.len()).max().unwrap_or(0);
+
+    branches
+        .iter()
+        .map(|b| format_branch_verbose_line(b, worktrees, level, width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_branch_verbose_line(
+    branch: &BranchInfo,
+    worktrees: &[WorktreeInfo],
+    level: u8,
+    width: usize,
+) -> String {
+    let worktree = find_worktree(branch, worktrees);
+
+    let prefix = if branch.is_head {
+        "* ".green().to_string()
+    } else if worktree.is_some() {
+        "+ ".cyan().to_string()
+    } else {
+        "  ".to_string()
+    };
+
+    let padded = format!("{:<width$}", branch.name, width = width);
+    let name = if branch.is_head {
+        padded.green().to_string()
+    } else if worktree.is_some() {
+        padded.cyan().to_string()
+    } else if branch.is_remote {
+        padded.red().to_string()
+    } else {
+        padded
+    };
+
+    let mut line = format!("{}{} {}", prefix, name, branch.short_sha.yellow());
+
+    if level >= 2 {
+        if let Some(wt) = worktree {
+            line.push_str(&format!(" ({})", wt.path.display().to_string().cyan()));
+        }
+    }
+
+    if let Some(tracking) = format_tracking(branch, level >= 2) {
+        line.push_str(&format!(" [{}]", tracking));
+    }
+    line.push_str(&format_remote_note(branch));
+
+    line.push(' ');
+    line.push_str(&branch.subject);
+    if !branch.date.is_empty() {
+        line.push_str(&format!(" ({})", branch.date).dimmed().to_string());
+    }
+
+    line
+}
+
+/// Format the bracketed upstream state, e.g. `origin/main: ahead 1, behind 2`.
+fn format_tracking(branch: &BranchInfo, with_upstream: bool) -> Option<String> {
+    let upstream = branch.upstream.as_deref()?;
+    let tracking = &branch.tracking;
+
+    let mut parts = Vec::new();
+    if tracking.gone {
+        parts.push("gone".red().to_string());
+    } else {
+        if tracking.ahead > 0 {
+            parts.push(format!("ahead {}", tracking.ahead.to_string().green()));
+        }
+        if tracking.behind > 0 {
+            parts.push(format!("behind {}", tracking.behind.to_string().red()));
+        }
+    }
+
+    match (with_upstream, parts.is_empty()) {
+        (true, true) => Some(upstream.blue().to_string()),
+        (true, false) => Some(format!("{}: {}", upstream.blue(), parts.join(", "))),
+        (false, true) => None,
+        (false, false) => Some(parts.join(", ")),
+    }
+}
+
+/// Find the (non-bare) worktree that has the branch checked out.
+fn find_worktree<'a>(
+    branch: &BranchInfo,
+    worktrees: &'a [WorktreeInfo],
+) -> Option<&'a WorktreeInfo> {
+    if branch.is_remote {
+        return None;
+    }
+    worktrees
+        .iter()
+        .find(|wt| wt.branch.as_deref() == Some(&branch.name) && !wt.is_bare)
+}
+
+/// A branch joined with its worktree, as serialized by `wb list --json`.
+#[derive(Serialize)]
+struct BranchEntry<'a> {
+    name: &'a str,
+    sha: &'a str,
+    is_current: bool,
+    is_remote: bool,
+    upstream: Option<&'a str>,
+    ahead: u32,
+    behind: u32,
+    upstream_gone: bool,
+    local_branch: Option<&'a str>,
+    subject: &'a str,
+    worktree: Option<WorktreeEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct WorktreeEntry<'a> {
+    path: String,
+    head: &'a str,
+    is_detached: bool,
+    is_bare: bool,
+}
+
+fn branch_entry<'a>(branch: &'a BranchInfo, worktrees: &'a [WorktreeInfo]) -> BranchEntry<'a> {
+    BranchEntry {
+        name: &branch.name,
+        sha: &branch.sha,
+        is_current: branch.is_head,
+        is_remote: branch.is_remote,
+        upstream: branch.upstream.as_deref(),
+        ahead: branch.tracking.ahead,
+        behind: branch.tracking.behind,
+        upstream_gone: branch.tracking.gone,
+        local_branch: branch.local_branch.as_deref(),
+        subject: &branch.subject,
+        worktree: find_worktree(branch, worktrees).map(|wt| WorktreeEntry {
+            path: wt.path.to_string_lossy().into_owned(),
+            head: &wt.head,
+            is_detached: wt.is_detached,
+            is_bare: wt.is_bare,
+        }),
+    }
+}
+
+/// Format branch listing as a pretty-printed JSON array.
+pub fn format_branch_json(branches: &[BranchInfo], worktrees: &[WorktreeInfo]) -> String {
+    let entries: Vec<BranchEntry> = branches
+        .iter()
+        .map(|b| branch_entry(b, worktrees))
+        .collect();
+    serde_json::to_string_pretty(&entries).expect("branch entries are always serializable")
+}
+
+/// Format branch listing as stable, tab-separated lines:
+/// `<name>\t<sha>\t<current>\t<worktree path>`
+///
+/// `<current>` is `*` for the current branch and `-` otherwise; the worktree
+/// path is empty when the branch has no worktree.
+pub fn format_branch_porcelain(branches: &[BranchInfo], worktrees: &[WorktreeInfo]) -> String {
+    branches
+        .iter()
+        .map(|b| {
+            let path = find_worktree(b, worktrees)
+                .map(|wt| wt.path.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let current = if b.is_head { "*" } else { "-" };
+            format!("{}\t{}\t{}\t{}", b.name, b.sha, current, path)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format branch listing with a user template, one line per branch.
+///
+/// `{current}` and `{dirty}` render as `*` or a space; fields that do not
+/// apply (e.g. `{path}` for a branch without a worktree) render empty.
+pub fn format_branch_template(
+    branches: &[BranchInfo],
+    worktrees: &[WorktreeInfo],
+    template: &Template,
+) -> String {
+    let check_dirty = template.uses(Placeholder::Dirty);
+
+    branches
+        .iter()
+        .map(|b| {
+            let worktree = find_worktree(b, worktrees);
+            let dirty = check_dirty && worktree.is_some_and(|wt| worktree::is_dirty(&wt.path));
+            let marker = |on: bool| if on { "*" } else { " " }.to_string();
+
+            template.render(|field| match field {
+                Placeholder::Branch => b.name.clone(),
+                Placeholder::Path => worktree
+                    .map(|wt| wt.path.display().to_string())
+                    .unwrap_or_default(),
+                Placeholder::Sha => b.short_sha.clone(),
+                Placeholder::Upstream => b.upstream.clone().unwrap_or_default(),
+                Placeholder::Ahead => b.tracking.ahead.to_string(),
+                Placeholder::Behind => b.tracking.behind.to_string(),
+                Placeholder::Subject => b.subject.clone(),
+                Placeholder::Current => marker(b.is_head),
+                Placeholder::Dirty => marker(dirty),
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A worktree as shown by `wb list --worktrees`.
+pub struct WorktreeView<'a> {
+    pub info: &'a WorktreeInfo,
+    /// The worktree contains the current directory.
+    pub is_current: bool,
+    /// The worktree's branch no longer exists.
+    pub is_orphaned: bool,
+}
+
+/// Format every worktree with its HEAD and state, similar to `git worktree list`,
+/// followed by stray directories git does not know about.
+pub fn format_worktree_list(worktrees: &[WorktreeView], stray: &[PathBuf]) -> String {
+    let width = worktrees
+        .iter()
+        .map(|wt| wt.info.path.display().to_string().len())
+        .chain(stray.iter().map(|p| p.display().to_string().len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<String> = worktrees
+        .iter()
+        .map(|wt| format_worktree_line(wt, width))
+        .collect();
+
+    for path in stray {
+        let padded = format!("{:<width$}", path.display(), width = width);
+        lines.push(format!(
+            "  {} {}",
+            padded.red(),
+            "(stray: not a registered worktree)".red()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn format_worktree_line(wt: &WorktreeView, width: usize) -> String {
+    let info = wt.info;
+    let prefix = if wt.is_current {
+        "* ".green().to_string()
+    } else {
+        "  ".to_string()
+    };
+
+    let padded = format!("{:<width$}", info.path.display(), width = width);
+    let path = if wt.is_current {
+        padded.green().to_string()
+    } else {
+        padded
+    };
+
+    let sha: String = info.head.chars().take(7).collect();
+    let mut line = format!("{}{} {}", prefix, path, sha.yellow());
+
+    match info.branch {
+        Some(ref branch) if wt.is_orphaned => {
+            line.push_str(&format!(" [{}] {}", branch, "(branch deleted)".red()));
+        }
+        Some(ref branch) => line.push_str(&format!(" [{}]", branch.cyan())),
+        None => line.push_str(&format!(" {}", "(detached HEAD)".red())),
+    }
+
+    if let Some(ref reason) = info.locked {
+        line.push_str(&format_state("locked", reason).yellow().to_string());
+    }
+    if let Some(ref reason) = info.prunable {
+        line.push_str(&format_state("prunable", reason).red().to_string());
+    }
+
+    line
+}
+
+fn format_state(state: &str, reason: &str) -> String {
+    if reason.is_empty() {
+        format!(" {}", state)
+    } else {
+        format!(" {} ({})", state, reason)
+    }
+}
+
+/// A worktree or stray directory, as serialized by `wb list --worktrees --json`.
+#[derive(Serialize)]
+struct WorktreeListEntry<'a> {
+    path: String,
+    head: Option<&'a str>,
+    branch: Option<&'a str>,
+    is_current: bool,
+    is_detached: bool,
+    is_orphaned: bool,
+    is_stray: bool,
+    locked: Option<&'a str>,
+    prunable: Option<&'a str>,
+}
+
+/// Format worktrees and stray directories as a pretty-printed JSON array.
+pub fn format_worktree_json(worktrees: &[WorktreeView], stray: &[PathBuf]) -> String {
+    let entries: Vec<WorktreeListEntry> = worktrees
+        .iter()
+        .map(|wt| WorktreeListEntry {
+            path: wt.info.path.to_string_lossy().into_owned(),
+            head: Some(&wt.info.head),
+            branch: wt.info.branch.as_deref(),
+            is_current: wt.is_current,
+            is_detached: wt.info.is_detached,
+            is_orphaned: wt.is_orphaned,
+            is_stray: false,
+            locked: wt.info.locked.as_deref(),
+            prunable: wt.info.prunable.as_deref(),
+        })
+        .chain(stray.iter().map(|path| WorktreeListEntry {
+            path: path.to_string_lossy().into_owned(),
+            head: None,
+            branch: None,
+            is_current: false,
+            is_detached: false,
+            is_orphaned: false,
+            is_stray: true,
+            locked: None,
+            prunable: None,
+        }))
+        .collect();
+    serde_json::to_string_pretty(&entries).expect("worktree entries are always serializable")
+}
+
+/// A worktree row in the `wb status` dashboard.
+pub struct StatusView<'a> {
+    pub info: &'a WorktreeInfo,
+    /// `None` if git could not read the worktree (e.g. its directory is gone).
+    pub status: Option<WorktreeStatus>,
+    pub stashes: usize,
+    pub is_current: bool,
+}
+
+/// Format the `wb status` dashboard: one line per worktree.
+pub fn format_status_list(views: &[StatusView]) -> String {
+    let labels: Vec<String> = views.iter().map(|v| worktree_label(v.info)).collect();
+    let width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    views
+        .iter()
+        .zip(&labels)
+        .map(|(view, label)| {
+            let prefix = if view.is_current {
+                "* ".green().to_string()
+            } else {
+                "  ".to_string()
+            };
+            let padded = format!("{:<width$}", label, width = width);
+            let name = if view.is_current {
+                padded.green().to_string()
+            } else {
+                padded
+            };
+            format!("{}{} {}", prefix, name, format_status_summary(view))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Branch name, or `(detached <sha>)` for a detached worktree.
+fn worktree_label(info: &WorktreeInfo) -> String {
+    match info.branch {
+        Some(ref branch) => branch.clone(),
+        None => format!(
+            "(detached {})",
+            info.head.chars().take(7).collect::<String>()
+        ),
+    }
+}
+
+fn format_status_summary(view: &StatusView) -> String {
+    let Some(ref status) = view.status else {
+        return "(missing)".red().to_string();
+    };
+
+    let mut parts = Vec::new();
+
+    if let Some(op) = status.operation {
+        parts.push(
+            format!("{} in progress", op.name())
+                .yellow()
+                .bold()
+                .to_string(),
+        );
+    }
+
+    if status.is_clean() {
+        parts.push("clean".green().to_string());
+    } else {
+        let counts = [
+            (status.conflicted, "conflicted", Color::Red),
+            (status.staged, "staged", Color::Green),
+            (status.modified, "modified", Color::Red),
+            (status.untracked, "untracked", Color::BrightBlack),
+        ];
+        let changes: Vec<String> = counts
+            .iter()
+            .filter(|(n, _, _)| *n > 0)
+            .map(|(n, label, color)| format!("{} {}", n, label).color(*color).to_string())
+            .collect();
+        parts.push(changes.join(", "));
+    }
+
+    let mut tracking = Vec::new();
+    if status.ahead > 0 {
+        tracking.push(format!("ahead {}", status.ahead.to_string().green()));
+    }
+    if status.behind > 0 {
+        tracking.push(format!("behind {}", status.behind.to_string().red()));
+    }
+    if !tracking.is_empty() {
+        parts.push(format!("[{}]", tracking.join(", ")));
+    }
+
+    if view.stashes > 0 {
+        let noun = if view.stashes == 1 {
+            "stash"
+        } else {
+            "stashes"
+        };
+        parts.push(format!("{} {}", view.stashes, noun).cyan().to_string());
+    }
+
+    parts.join("  ")
 }
@@ -0,0 +1,214 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::git;
+use crate::worktree;
+
+/// The worktree new files are taken from: `branch`'s worktree if it has one,
+/// else the worktree the user is in.
+pub fn source_worktree(branch: Option<&str>) -> Option<PathBuf> {
+    if let Some(branch) = branch {
+        if let Ok(Some(wt)) = worktree::find_worktree_for_branch(branch) {
+            return Some(wt.path);
+        }
+    }
+    let cwd = std::env::current_dir().ok()?;
+    worktree::find_worktree_for_path(&cwd)
+        .ok()
+        .flatten()
+        .filter(|wt| !wt.is_bare)
+        .map(|wt| wt.path)
+}
+
+/// How files are brought into a new worktree, cheapest first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CloneMethod {
+    /// Copy-on-write clone; shares blocks until either side writes.
+    Reflink,
+    /// Hard link; both worktrees share the same file.
+    Hardlink,
+    /// Plain byte copy.
+    Copy,
+}
+
+impl CloneMethod {
+    pub fn name(self) -> &'static str {
+        match self {
+            CloneMethod::Reflink => "reflink",
+            CloneMethod::Hardlink => "hardlink",
+            CloneMethod::Copy => "copy",
+        }
+    }
+}
+
+/// Copy the ignored files and directories in `src` matching `patterns` into
+/// `dst`, without overwriting anything already there. Returns how many
+/// top-level matches were copied.
+///
+/// Patterns are globs relative to the worktree root (`**` crosses directories).
+pub fn copy_ignored(src: &Path, dst: &Path, patterns: &[String]) -> Result<usize> {
+    let mut method = CloneMethod::Copy;
+    Ok(clone_ignored(src, dst, patterns, &mut method)?.len())
+}
+
+/// Clone the ignored paths in `src` matching `patterns` into `dst`, trying
+/// reflinks first and falling back to hard links, then plain copies.
+/// Returns the cloned paths and the cheapest method that worked for all of them.
+pub fn warm(src: &Path, dst: &Path, patterns: &[String]) -> Result<(Vec<String>, CloneMethod)> {
+    let mut method = CloneMethod::Reflink;
+    let cloned = clone_ignored(src, dst, patterns, &mut method)?;
+    Ok((cloned, method))
+}
+
+fn clone_ignored(
+    src: &Path,
+    dst: &Path,
+    patterns: &[String],
+    method: &mut CloneMethod,
+) -> Result<Vec<String>> {
+    if patterns.is_empty() || src == dst {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates = Vec::new();
+    collect_matches(src, "", patterns, &mut candidates)?;
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+    candidates.sort();
+
+    // Only ignored paths; tracked and plain untracked files are left alone
+    let mut args = vec!["check-ignore", "--"];
+    args.extend(candidates.iter().map(String::as_str));
+    let ignored = git::run_in(src, &args).unwrap_or_default();
+
+    let mut cloned = Vec::new();
+    for rel in ignored.lines() {
+        let target = dst.join(rel);
+        if fs::symlink_metadata(&target).is_ok() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        clone_tree(&src.join(rel), &target, method)?;
+        cloned.push(rel.to_string());
+    }
+
+    Ok(cloned)
+}
+
+/// Walk `root/rel_dir`, collecting paths that match a pattern and descending
+/// only into directories that could contain a match.
+fn collect_matches(
+    root: &Path,
+    rel_dir: &str,
+    patterns: &[String],
+    out: &mut Vec<String>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(root.join(rel_dir))? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if rel_dir.is_empty() && name == ".git" {
+            continue;
+        }
+        let rel = if rel_dir.is_empty() {
+            name
+        } else {
+            format!("{}/{}", rel_dir, name)
+        };
+
+        if patterns.iter().any(|p| glob_match::glob_match(p, &rel)) {
+            out.push(rel);
+        } else if entry.file_type()?.is_dir() && patterns.iter().any(|p| may_match_under(p, &rel)) {
+            collect_matches(root, &rel, patterns, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether some path below directory `dir` could match `pattern`.
+fn may_match_under(pattern: &str, dir: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('/').collect();
+    for (i, component) in dir.split('/').enumerate() {
+        match parts.get(i) {
+            Some(part) if part.contains("**") => return true,
+            // The last pattern component names the match itself, not a parent
+            Some(part) if i + 1 < parts.len() => {
+                if !glob_match::glob_match(part, component) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Clone a file, symlink or directory tree, skipping anything that already exists.
+fn clone_tree(src: &Path, dst: &Path, method: &mut CloneMethod) -> io::Result<()> {
+    let meta = fs::symlink_metadata(src)?;
+    if meta.file_type().is_symlink() {
+        copy_symlink(src, dst)
+    } else if meta.is_dir() {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let target = dst.join(entry.file_name());
+            if fs::symlink_metadata(&target).is_err() {
+                clone_tree(&entry.path(), &target, method)?;
+            }
+        }
+        Ok(())
+    } else {
+        clone_file(src, dst, method)
+    }
+}
+
+/// Clone one file, downgrading `method` for good once it fails
+/// (e.g. no reflink support, or a hard link across filesystems).
+fn clone_file(src: &Path, dst: &Path, method: &mut CloneMethod) -> io::Result<()> {
+    if *method == CloneMethod::Reflink {
+        if reflink_copy::reflink(src, dst).is_ok() {
+            return Ok(());
+        }
+        *method = CloneMethod::Hardlink;
+    }
+    if *method == CloneMethod::Hardlink {
+        if fs::hard_link(src, dst).is_ok() {
+            return Ok(());
+        }
+        *method = CloneMethod::Copy;
+    }
+    fs::copy(src, dst).map(|_| ())
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::copy(src, dst).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_may_match_under() {
+        assert!(may_match_under("config/local.yml", "config"));
+        assert!(!may_match_under("config/local.yml", "src"));
+        assert!(!may_match_under("config/local.yml", "config/deep"));
+        assert!(may_match_under("apps/*/.env", "apps/web"));
+        assert!(!may_match_under(".env", "apps"));
+        assert!(may_match_under("**/.env", "apps/web/deep"));
+        assert!(may_match_under("apps/**/.env", "apps/web"));
+        assert!(!may_match_under("apps/**/.env", "lib"));
+    }
+}
@@ -1,6 +1,14 @@
 use std::path::PathBuf;
 
+use anyhow::{bail, Result};
+
 use crate::config::{NamingConvention, WbConfig};
+use crate::error::WbError;
+use crate::git;
+use crate::terminal;
+
+/// How many ranked matches a prompt or error lists.
+const MAX_SUGGESTIONS: usize = 10;
 
 /// Convert a branch name to a worktree directory path.
 pub fn branch_to_worktree_path(config: &WbConfig, branch: &str) -> PathBuf {
@@ -30,6 +38,170 @@ pub fn dir_name_to_branch(dir_name: &str, naming: &NamingConvention) -> String {
     }
 }
 
+/// Resolve a branch argument to an existing local branch: the exact name, else
+/// the best fuzzy match if it is unique, else the user's pick from a ranked list.
+pub fn existing_branch(query: &str) -> Result<String> {
+    match find_branch(query, false)? {
+        Some(branch) => Ok(branch),
+        None => Err(WbError::BranchNotFound(query.to_string()).into()),
+    }
+}
+
+/// Like [`existing_branch`], but for a name that may be new: returns `None`
+/// when nothing matches or the user chooses to create `query` instead.
+/// Only substring matches count, so new names are not swallowed by loose ones,
+/// and without a terminal an ambiguous name is taken as new.
+pub fn find_branch(query: &str, allow_new: bool) -> Result<Option<String>> {
+    if git::branch_exists(query) {
+        return Ok(Some(query.to_string()));
+    }
+
+    let names = git::local_branch_names()?;
+    let mut ranked = rank_matches(query, &names);
+    if allow_new {
+        ranked.retain(|(tier, _)| *tier < MatchTier::Subsequence);
+    }
+    let Some(&(best, _)) = ranked.first() else {
+        return Ok(None);
+    };
+
+    // A name that may be new is offered as a choice even next to a unique
+    // match, so it can be created on a terminal
+    let unique = ranked.iter().filter(|(tier, _)| *tier == best).count() == 1;
+    let interactive = terminal::is_interactive();
+    if unique && !(allow_new && interactive) {
+        let branch = ranked[0].1.to_string();
+        eprintln!("'{}' matched '{}'", query, branch);
+        return Ok(Some(branch));
+    }
+
+    if !interactive {
+        if allow_new {
+            return Ok(None);
+        }
+        bail!(
+            "fatal: '{}' matches several branches:\n  {}",
+            query,
+            suggestions(&ranked).join("\n  ")
+        );
+    }
+    let options = if unique {
+        vec![ranked[0].1.to_string()]
+    } else {
+        suggestions(&ranked)
+    };
+    choose_branch(query, options, allow_new)
+}
+
+/// Like [`existing_branch`], for commands that merge, move or delete the
+/// branch: anything but the exact name has to be confirmed on a terminal,
+/// and is refused when there is none.
+pub fn confirmed_branch(query: &str) -> Result<String> {
+    if git::branch_exists(query) {
+        return Ok(query.to_string());
+    }
+
+    let names = git::local_branch_names()?;
+    let ranked = rank_matches(query, &names);
+    let Some(&(best, first)) = ranked.first() else {
+        return Err(WbError::BranchNotFound(query.to_string()).into());
+    };
+    let options = suggestions(&ranked);
+
+    if !terminal::is_interactive() {
+        bail!(
+            "fatal: no branch is named '{}'; give the exact name of one of:\n  {}",
+            query,
+            options.join("\n  ")
+        );
+    }
+    if ranked.iter().filter(|(tier, _)| *tier == best).count() == 1 {
+        if terminal::confirm(&format!("'{}' matched '{}'. Use it?", query, first))? {
+            return Ok(first.to_string());
+        }
+        bail!("Aborted.");
+    }
+    match choose_branch(query, options, false)? {
+        Some(branch) => Ok(branch),
+        None => bail!("Aborted."),
+    }
+}
+
+fn suggestions(ranked: &[(MatchTier, &str)]) -> Vec<String> {
+    ranked
+        .iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+/// Ask which of `options` the user meant, offering to create `query` instead
+/// when `allow_new` is set.
+fn choose_branch(query: &str, mut options: Vec<String>, allow_new: bool) -> Result<Option<String>> {
+    let branches = options.len();
+    if allow_new {
+        options.push(format!("create new branch '{}'", query));
+    }
+    let question = if branches == 1 {
+        format!("'{}' matches an existing branch:", query)
+    } else {
+        format!("'{}' matches several branches:", query)
+    };
+    match terminal::choose(&question, &options)? {
+        Some(i) if i < branches => Ok(Some(options.swap_remove(i))),
+        Some(_) => Ok(None),
+        None => bail!("Aborted."),
+    }
+}
+
+/// How closely a branch name matches a query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchTier {
+    /// The query starts the name or one of its `/`, `-`, `_`, `.` separated words.
+    WordPrefix,
+    /// The query appears somewhere in the name.
+    Substring,
+    /// The query's characters appear in the name in order.
+    Subsequence,
+}
+
+/// The names matching `query` (case-insensitively), ranked by tier, then by length.
+fn rank_matches<'a>(query: &str, names: &'a [String]) -> Vec<(MatchTier, &'a str)> {
+    let query = query.to_lowercase();
+    let mut ranked: Vec<(MatchTier, &str)> = names
+        .iter()
+        .filter_map(|name| Some((match_tier(&query, &name.to_lowercase())?, name.as_str())))
+        .collect();
+    ranked.sort_by_key(|&(tier, name)| (tier, name.len(), name));
+    ranked
+}
+
+fn match_tier(query: &str, name: &str) -> Option<MatchTier> {
+    if query.is_empty() {
+        return None;
+    }
+    let mut substring = false;
+    for (i, _) in name.match_indices(query) {
+        let at_word = name[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| "/-_.".contains(c));
+        if at_word {
+            return Some(MatchTier::WordPrefix);
+        }
+        substring = true;
+    }
+    if substring {
+        return Some(MatchTier::Substring);
+    }
+
+    let mut chars = name.chars();
+    query
+        .chars()
+        .all(|q| chars.any(|c| c == q))
+        .then_some(MatchTier::Subsequence)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,11 +238,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rank_matches() {
+        let names: Vec<String> = ["main", "feature/auth-refactor", "oauth", "fix/a-u-t-h"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            rank_matches("auth", &names),
+            vec![
+                (MatchTier::WordPrefix, "feature/auth-refactor"),
+                (MatchTier::Substring, "oauth"),
+                (MatchTier::Subsequence, "fix/a-u-t-h"),
+            ]
+        );
+        assert_eq!(
+            rank_matches("AUTH-R", &names),
+            vec![(MatchTier::WordPrefix, "feature/auth-refactor")]
+        );
+        assert!(rank_matches("xyz", &names).is_empty());
+        assert!(rank_matches("", &names).is_empty());
+    }
+
     #[test]
     fn test_worktree_path() {
         let config = WbConfig {
             worktree_dir: PathBuf::from("/home/user/project"),
             naming: NamingConvention::Flat,
+            list_format: None,
+            base: None,
+            land_strategy: Default::default(),
+            copy_files: Vec::new(),
+            warm_dirs: Vec::new(),
+            env_file: None,
+            port_base: 3000,
+            port_span: 10,
+            multiplexer: Default::default(),
+            session_windows: Vec::new(),
+            session_layout: None,
+            session_on_create: false,
+            session_on_delete: false,
+            editor: None,
+            workspace_file: None,
         };
         assert_eq!(
             branch_to_worktree_path(&config, "feature/auth"),
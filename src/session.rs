@@ -0,0 +1,182 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::{Multiplexer, WbConfig};
+use crate::terminal;
+
+/// The session name for a branch, with characters the multiplexer rejects replaced.
+pub fn session_name(mux: Multiplexer, branch: &str) -> String {
+    let reserved: &[char] = match mux {
+        Multiplexer::Tmux => &['.', ':'],
+        Multiplexer::Zellij => &['.', ':', '/'],
+    };
+    branch.replace(reserved, "_")
+}
+
+/// Check whether the multiplexer has a session with this exact name.
+pub fn exists(mux: Multiplexer, name: &str) -> bool {
+    match mux {
+        Multiplexer::Tmux => output("tmux", &["has-session", "-t", &exact(name)]).is_ok(),
+        Multiplexer::Zellij => output("zellij", &["list-sessions", "--short"])
+            .is_ok_and(|out| out.lines().any(|l| l.trim() == name)),
+    }
+}
+
+/// Start a detached session in `path`, laid out from `wb.sessionWindows` (tmux).
+pub fn spawn(config: &WbConfig, name: &str, path: &Path) -> Result<()> {
+    match config.multiplexer {
+        Multiplexer::Tmux => {
+            let dir = path.to_string_lossy();
+            let mut windows = config
+                .session_windows
+                .iter()
+                .map(|w| match w.split_once('=') {
+                    Some((window, cmd)) => (window.trim(), Some(cmd.trim())),
+                    None => (w.trim(), None),
+                });
+
+            let mut args = vec!["new-session", "-d", "-s", name, "-c", &dir];
+            let first = windows.next();
+            if let Some((window, _)) = first {
+                args.extend(["-n", window]);
+            }
+            output("tmux", &args)?;
+
+            for (i, (window, cmd)) in first.into_iter().chain(windows).enumerate() {
+                if i > 0 {
+                    let session = format!("{}:", exact(name));
+                    output(
+                        "tmux",
+                        &["new-window", "-d", "-t", &session, "-n", window, "-c", &dir],
+                    )?;
+                }
+                if let Some(cmd) = cmd {
+                    let target = format!("{}:{}", exact(name), window);
+                    output("tmux", &["send-keys", "-t", &target, cmd, "Enter"])?;
+                }
+            }
+        }
+        Multiplexer::Zellij => {
+            let out = Command::new("zellij")
+                .args(["attach", "--create-background", name])
+                .current_dir(path)
+                .stdin(Stdio::null())
+                .output()
+                .context("failed to run zellij")?;
+            if !out.status.success() {
+                bail!("{}", String::from_utf8_lossy(&out.stderr).trim());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Attach to the session (switching to it from inside tmux), creating it first if needed.
+pub fn attach(config: &WbConfig, name: &str, path: &Path) -> Result<()> {
+    let mux = config.multiplexer;
+    let mut cmd = match mux {
+        Multiplexer::Tmux => {
+            if !exists(mux, name) {
+                spawn(config, name, path)?;
+            }
+            let verb = if std::env::var_os("TMUX").is_some() {
+                "switch-client"
+            } else {
+                "attach-session"
+            };
+            let mut cmd = Command::new("tmux");
+            cmd.args([verb, "-t", &exact(name)]);
+            cmd
+        }
+        Multiplexer::Zellij => {
+            let mut cmd = Command::new("zellij");
+            if exists(mux, name) {
+                cmd.args(["attach", name]);
+            } else {
+                cmd.args(["--session", name]);
+                if let Some(ref layout) = config.session_layout {
+                    cmd.args(["--layout", layout]);
+                }
+            }
+            cmd.current_dir(path);
+            cmd
+        }
+    };
+
+    terminal::stdout_to_tty(&mut cmd);
+
+    let status = cmd
+        .status()
+        .with_context(|| format!("failed to run {}", mux.name()))?;
+    if !status.success() {
+        bail!(
+            "fatal: {} could not attach to session '{}'",
+            mux.name(),
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Check whether wb is running inside this session.
+fn is_current(mux: Multiplexer, name: &str) -> bool {
+    match mux {
+        Multiplexer::Tmux => {
+            std::env::var_os("TMUX").is_some()
+                && output("tmux", &["display-message", "-p", "#S"]).is_ok_and(|s| s == name)
+        }
+        Multiplexer::Zellij => std::env::var("ZELLIJ_SESSION_NAME").is_ok_and(|s| s == name),
+    }
+}
+
+/// Kill the session if it exists, unless wb is running inside it.
+pub fn kill(mux: Multiplexer, name: &str) -> Result<()> {
+    if !exists(mux, name) {
+        return Ok(());
+    }
+    if is_current(mux, name) {
+        bail!("it is the session you are attached to");
+    }
+    match mux {
+        Multiplexer::Tmux => output("tmux", &["kill-session", "-t", &exact(name)])?,
+        Multiplexer::Zellij => output("zellij", &["delete-session", "--force", name])?,
+    };
+    Ok(())
+}
+
+/// A tmux target that matches the session name exactly rather than as a prefix.
+fn exact(name: &str) -> String {
+    format!("={}", name)
+}
+
+fn output(program: &str, args: &[&str]) -> Result<String> {
+    let out = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("failed to run {}", program))?;
+    if !out.status.success() {
+        bail!("{}", String::from_utf8_lossy(&out.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_name() {
+        assert_eq!(
+            session_name(Multiplexer::Tmux, "feature/v1.2"),
+            "feature/v1_2"
+        );
+        assert_eq!(
+            session_name(Multiplexer::Zellij, "feature/v1.2"),
+            "feature_v1_2"
+        );
+        assert_eq!(session_name(Multiplexer::Tmux, "a:b"), "a_b");
+    }
+}
@@ -1,15 +1,28 @@
+use super::Key;
+
 pub const SHELL_INIT: &str = r#"# wb shell integration for bash
 # Add to .bashrc: eval "$(wb init bash)"
 
-wb() {
-  local result
-  result=$(command wb "$@")
-  local exit_code=$?
+{{cmd}}() {
+  # wb writes directives such as `__wb_cd:<dir>` to this file, leaving its
+  # stdout on the terminal
+  local directive_file exit_code line target
+  directive_file=$(mktemp "${TMPDIR:-/tmp}/wb.XXXXXX") || return 1
+  # Ctrl-C would otherwise abandon the function and leave the file behind
+  trap 'command rm -f "$directive_file"; trap - INT; return 130' INT
+  WB_DIRECTIVE_FILE="$directive_file" command wb "$@"
+  exit_code=$?
+  trap - INT
+
+  while IFS= read -r line; do
+    if [[ "$line" == __wb_cd:* ]]; then
+      target="${line#__wb_cd:}"
+    fi
+  done < "$directive_file"
+  command rm -f "$directive_file"
 
-  if [[ "$result" == __wb_cd:* ]]; then
-    builtin cd "${result#__wb_cd:}"
-  elif [[ -n "$result" ]]; then
-    echo "$result"
+  if [[ -n "$target" ]]; then
+    builtin cd "$target"
   fi
 
   return $exit_code
@@ -22,23 +35,38 @@ _wb_completions() {
   cur="${COMP_WORDS[COMP_CWORD]}"
   prev="${COMP_WORDS[COMP_CWORD-1]}"
 
-  subcmds="init list create delete rename copy"
+  subcmds="init list status sync clean land restack session open workspace ui back recent env foreach create delete rename copy"
 
   if [[ ${COMP_CWORD} -eq 1 ]]; then
-    COMPREPLY=( $(compgen -W "$subcmds" -- "$cur") )
+    if [[ "$cur" == -* ]]; then
+      COMPREPLY=( $(compgen -W "-d -D -m -M -c -C -l -r -a -v -vv --show-current" -- "$cur") )
+    else
+      COMPREPLY=( $(compgen -W "$subcmds" -- "$cur") )
+    fi
     return 0
   fi
 
   local subcmd="${COMP_WORDS[1]}"
 
   case "$subcmd" in
+    -d|-D|--delete|-m|-M|--move|-c|-C|--copy|-l|--list)
+      branches=$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
+      COMPREPLY=( $(compgen -W "$branches" -- "$cur") )
+      return 0
+      ;;
     init)
-      if [[ ${COMP_CWORD} -eq 2 ]]; then
+      if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "--directory --cmd --bind" -- "$cur") )
+      elif [[ ${COMP_CWORD} -eq 2 ]]; then
         COMPREPLY=( $(compgen -W "zsh bash fish" -- "$cur") )
       fi
       return 0
       ;;
-    create|rename|copy)
+    create|rename|copy|session|open)
+      if [[ "$subcmd" == create && "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "--warm" -- "$cur") )
+        return 0
+      fi
       branches=$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
       COMPREPLY=( $(compgen -W "$branches" -- "$cur") )
       return 0
@@ -52,15 +80,72 @@ _wb_completions() {
       fi
       return 0
       ;;
+    clean)
+      COMPREPLY=( $(compgen -W "--force --dry-run --yes --no-fetch" -- "$cur") )
+      return 0
+      ;;
+    land)
+      if [[ "$prev" == --strategy ]]; then
+        COMPREPLY=( $(compgen -W "ff merge rebase" -- "$cur") )
+      elif [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "--base --strategy" -- "$cur") )
+      else
+        branches=$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
+        COMPREPLY=( $(compgen -W "$branches" -- "$cur") )
+      fi
+      return 0
+      ;;
+    env)
+      if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "--write" -- "$cur") )
+      else
+        branches=$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
+        COMPREPLY=( $(compgen -W "$branches" -- "$cur") )
+      fi
+      return 0
+      ;;
+    recent)
+      if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "--list" -- "$cur") )
+      fi
+      return 0
+      ;;
+    foreach)
+      COMPREPLY=( $(compgen -W "--pattern --jobs --keep-going --" -- "$cur") )
+      return 0
+      ;;
     list)
+      if [[ "$cur" != -* ]]; then
+        branches=$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
+        COMPREPLY=( $(compgen -W "$branches" -- "$cur") )
+        return 0
+      fi
+      COMPREPLY=( $(compgen -W "-r -a --remotes --all -v -vv --verbose --json --porcelain --format --stack --worktrees --sort --merged --no-merged --contains --no-contains --landed --mine" -- "$cur") )
       return 0
       ;;
   esac
 }
-complete -F _wb_completions wb
+complete -F _wb_completions {{cmd}}
 
 # Prompt helper
 wb_current_branch() {
   command git branch --show-current 2>/dev/null
 }
 "#;
+
+/// Key binding that opens `wb ui` and cds into the worktree picked there.
+pub const BIND: &str = r#"
+# Key binding: pick a branch in wb ui and cd into its worktree
+__wb_pick() {
+  {{cmd}} ui < /dev/tty
+}
+bind -x '"{{key}}": __wb_pick'
+"#;
+
+/// `ctrl-g` as `\C-g`, `alt-g` as `\eg`.
+pub fn key_notation(key: Key) -> String {
+    match key {
+        Key::Ctrl(c) => format!("\\C-{}", c),
+        Key::Alt(c) => format!("\\e{}", c),
+    }
+}
@@ -1,43 +1,146 @@
+use super::Key;
+
 pub const SHELL_INIT: &str = r#"# wb shell integration for fish
 # Add to config.fish: wb init fish | source
 
-function wb --wraps='command wb'
-    set -l result (command wb $argv)
+function {{cmd}} --wraps='command wb'
+    # wb writes directives such as `__wb_cd:<dir>` to this file, leaving its
+    # stdout on the terminal
+    set -l tmpdir /tmp
+    set -q TMPDIR; and set tmpdir $TMPDIR
+    set -l directive_file (mktemp "$tmpdir/wb.XXXXXX"); or return 1
+    # Ctrl-C cancels the function, so only a signal handler can remove the file
+    function __{{cmd}}_remove_directive_file --on-signal INT --inherit-variable directive_file
+        command rm -f $directive_file
+        functions -e __{{cmd}}_remove_directive_file
+    end
+    WB_DIRECTIVE_FILE=$directive_file command wb $argv
     set -l exit_code $status
+    functions -e __{{cmd}}_remove_directive_file
 
-    if string match -q '__wb_cd:*' "$result"
-        set -l dir (string replace '__wb_cd:' '' "$result")
-        builtin cd "$dir"
-    else if test -n "$result"
-        echo "$result"
+    set -l target
+    while read -l line
+        if string match -q '__wb_cd:*' -- "$line"
+            set target (string replace '__wb_cd:' '' -- "$line")
+        end
+    end < $directive_file
+    command rm -f $directive_file
+
+    if test -n "$target"
+        builtin cd "$target"
     end
 
     return $exit_code
 end
 
 # Fish completions
-complete -c wb -f
+complete -c {{cmd}} -f
 
 # Subcommands (only when no subcommand given yet)
-complete -c wb -n '__fish_use_subcommand' -a init -d 'Initialize (shell integration or clone)'
-complete -c wb -n '__fish_use_subcommand' -a list -d 'List local branches'
-complete -c wb -n '__fish_use_subcommand' -a create -d 'Create a branch with worktree'
-complete -c wb -n '__fish_use_subcommand' -a delete -d 'Delete branch(es) and worktrees'
-complete -c wb -n '__fish_use_subcommand' -a rename -d 'Rename a branch and move worktree'
-complete -c wb -n '__fish_use_subcommand' -a copy -d 'Copy a branch and create worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a init -d 'Initialize (shell integration or clone)'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a list -d 'List local branches'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a status -d 'Show the state of every worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a sync -d 'Fetch and fast-forward branches behind upstream'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a clean -d 'Delete merged and upstream-gone branches'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a land -d 'Merge a branch into its base and remove it'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a restack -d 'Rebase stacked branches onto their parents'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a session -d 'Attach to a tmux/zellij session for a branch'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a open -d 'Open a branch worktree in the editor'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a workspace -d 'Write a .code-workspace with every worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a ui -d 'Browse branches in a full-screen dashboard'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a back -d 'Go back to the previous worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a recent -d 'List or jump to recent worktrees'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a env -d 'Print worktree ports and variables'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a foreach -d 'Run a command in every worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a create -d 'Create a branch with worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a delete -d 'Delete branch(es) and worktrees'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a rename -d 'Rename a branch and move worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -a copy -d 'Copy a branch and create worktree'
+
+# git-branch-style flags
+complete -c {{cmd}} -n '__fish_use_subcommand' -s d -l delete -d 'Delete branch(es) and worktrees'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s D -d 'Force delete branch(es) and worktrees'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s m -l move -d 'Rename a branch and move worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s M -d 'Force rename a branch and move worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s c -l copy -d 'Copy a branch and create worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s C -d 'Force copy a branch and create worktree'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s l -l list -d 'List branches matching patterns'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s r -l remotes -d 'List remote-tracking branches'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s a -l all -d 'List local and remote-tracking branches'
+complete -c {{cmd}} -n '__fish_use_subcommand' -s v -l verbose -d 'List branches verbosely'
+complete -c {{cmd}} -n '__fish_use_subcommand' -l show-current -d 'Print the current branch'
+complete -c {{cmd}} -n '__fish_seen_argument -s d -s D -s m -s M -s c -s C -s l' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
 
 # init subcommand
-complete -c wb -n '__fish_seen_subcommand_from init' -a 'zsh bash fish'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from init' -a 'zsh bash fish'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from init' -l cmd -x -d 'Name of the shell wrapper function'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from init' -l bind -x -d 'Key that opens a branch picker'
+
+# create/rename/copy/session/open: branch completions
+complete -c {{cmd}} -n '__fish_seen_subcommand_from create rename copy session open' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+
+# create flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from create' -l warm -d 'Clone build artifacts from another worktree'
+
+# clean flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from clean' -s f -l force -d 'Also remove worktrees with local changes'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from clean' -s n -l dry-run -d 'Only show what would be deleted'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from clean' -s y -l yes -d 'Do not ask for confirmation'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from clean' -l no-fetch -d 'Skip fetching remotes first'
 
-# create/rename/copy: branch completions
-complete -c wb -n '__fish_seen_subcommand_from create rename copy' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+# land flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from land' -l base -x -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)' -d 'Base branch to land into'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from land' -l strategy -x -a 'ff merge rebase' -d 'How to integrate the branch'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from land' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+
+# env flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from env' -l write -d 'Also rewrite the worktree env file'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from recent' -s l -l list -d 'Only print the list'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from env' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+
+# foreach flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from foreach' -l pattern -x -d 'Only worktrees whose branch matches glob'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from foreach' -s j -l jobs -x -d 'Number of parallel commands'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from foreach' -s k -l keep-going -d 'Keep going after a failure'
+
+# list flags
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -s r -l remotes -d 'List remote-tracking branches'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -s a -l all -d 'List local and remote-tracking branches'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -s v -l verbose -d 'Show sha, tracking and subject'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l sort -x -d 'Sort by for-each-ref key'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l merged -d 'Only branches merged into commit'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l no-merged -d 'Only branches not merged into commit'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l contains -d 'Only branches containing commit'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l no-contains -d 'Only branches not containing commit'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l landed -d 'Only branches whose changes are in base'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l mine -d 'Only branches committed by you'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l format -x -d 'Format each branch with a template'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l stack -d 'Show stacked branches as a tree'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l worktrees -d 'List every worktree with its state'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l json -d 'Print branches as JSON'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from list' -l porcelain -d 'Print branches in a stable format'
 
 # delete: --force flag + branch completions
-complete -c wb -n '__fish_seen_subcommand_from delete' -l force -d 'Force delete'
-complete -c wb -n '__fish_seen_subcommand_from delete' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from delete' -l force -d 'Force delete'
+complete -c {{cmd}} -n '__fish_seen_subcommand_from delete' -a '(command git for-each-ref --format="%(refname:short)" refs/heads/ 2>/dev/null)'
 
 # Prompt helper
 function wb_current_branch
     command git branch --show-current 2>/dev/null
 end
 "#;
+
+/// Key binding that opens `wb ui` and cds into the worktree picked there.
+pub const BIND: &str = r#"
+# Key binding: pick a branch in wb ui and cd into its worktree
+bind {{key}} '{{cmd}} ui < /dev/tty; commandline -f repaint'
+"#;
+
+/// `ctrl-g` as `\cg`, `alt-g` as `\eg`.
+pub fn key_notation(key: Key) -> String {
+    match key {
+        Key::Ctrl(c) => format!("\\c{}", c),
+        Key::Alt(c) => format!("\\e{}", c),
+    }
+}
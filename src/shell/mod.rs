@@ -2,23 +2,145 @@ pub mod bash;
 pub mod fish;
 pub mod zsh;
 
-use anyhow::{bail, Result};
-
-/// Output shell integration code for the given shell.
-pub fn output_shell_init(shell: &str) -> Result<()> {
-    match shell {
-        "zsh" => {
-            print!("{}", zsh::SHELL_INIT);
-            Ok(())
-        }
-        "bash" => {
-            print!("{}", bash::SHELL_INIT);
-            Ok(())
-        }
-        "fish" => {
-            print!("{}", fish::SHELL_INIT);
-            Ok(())
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::history;
+
+/// Environment variable naming the file the shell wrapper reads directives from.
+const DIRECTIVE_FILE_ENV: &str = "WB_DIRECTIVE_FILE";
+
+static DIRECTIVE_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Take the wrapper's directive file out of the environment, so the hooks,
+/// editors and commands wb runs cannot send directives to the shell.
+pub fn take_directive_file() {
+    let file = std::env::var_os(DIRECTIVE_FILE_ENV)
+        .filter(|f| !f.is_empty())
+        .map(PathBuf::from);
+    std::env::remove_var(DIRECTIVE_FILE_ENV);
+    let _ = DIRECTIVE_FILE.set(file);
+}
+
+/// Tell the shell wrapper to cd into `path`, remembering it for `wb back`.
+///
+/// The directive goes to `$WB_DIRECTIVE_FILE` when the wrapper provides one,
+/// leaving stdout to the user; older wrappers look for it on stdout instead.
+pub fn cd(path: &Path) {
+    history::record(path);
+    let directive = format!("__wb_cd:{}", path.display());
+    if let Some(Some(file)) = DIRECTIVE_FILE.get() {
+        if append_line(file, &directive).is_ok() {
+            return;
         }
+    }
+    println!("{}", directive);
+}
+
+fn append_line(file: &Path, line: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().append(true).create(true).open(file)?;
+    writeln!(file, "{}", line)
+}
+
+/// A key for `wb init <shell> --bind`, given as `ctrl-<letter>` or `alt-<letter>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Ctrl(char),
+    Alt(char),
+}
+
+impl Key {
+    pub fn parse(spec: &str) -> Result<Key> {
+        let lower = spec.to_ascii_lowercase();
+        let key = match lower.split_once('-') {
+            Some(("ctrl", letter)) => single_letter(letter).map(Key::Ctrl),
+            Some(("alt", letter)) => single_letter(letter).map(Key::Alt),
+            _ => None,
+        };
+        key.ok_or_else(|| {
+            anyhow!(
+                "fatal: unsupported key '{}'; use ctrl-<letter> or alt-<letter>",
+                spec
+            )
+        })
+    }
+}
+
+fn single_letter(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_lowercase() => Some(c),
+        _ => None,
+    }
+}
+
+/// Output shell integration code for the given shell, defining the wrapper
+/// function as `cmd` and optionally binding `bind` to a branch picker.
+pub fn output_shell_init(shell: &str, cmd: &str, bind: Option<&str>) -> Result<()> {
+    print!("{}", render_init(shell, cmd, bind)?);
+    Ok(())
+}
+
+fn render_init(shell: &str, cmd: &str, bind: Option<&str>) -> Result<String> {
+    let valid_cmd = cmd
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !cmd.is_empty()
+        && !cmd.starts_with('-');
+    if !valid_cmd {
+        bail!("fatal: invalid command name '{}'", cmd);
+    }
+    let key = bind.map(Key::parse).transpose()?;
+
+    let (init, bind_template, notation): (&str, &str, fn(Key) -> String) = match shell {
+        "zsh" => (zsh::SHELL_INIT, zsh::BIND, zsh::key_notation),
+        "bash" => (bash::SHELL_INIT, bash::BIND, bash::key_notation),
+        "fish" => (fish::SHELL_INIT, fish::BIND, fish::key_notation),
         _ => bail!("unsupported shell: '{}'. Supported: zsh, bash, fish", shell),
+    };
+
+    let mut script = init.replace("{{cmd}}", cmd);
+    if let Some(key) = key {
+        script.push_str(
+            &bind_template
+                .replace("{{cmd}}", cmd)
+                .replace("{{key}}", &notation(key)),
+        );
+    }
+    Ok(script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(Key::parse("ctrl-g").unwrap(), Key::Ctrl('g'));
+        assert_eq!(Key::parse("Alt-W").unwrap(), Key::Alt('w'));
+        assert!(Key::parse("ctrl-").is_err());
+        assert!(Key::parse("ctrl-gg").is_err());
+        assert!(Key::parse("shift-g").is_err());
+    }
+
+    #[test]
+    fn test_render_init() {
+        for shell in ["bash", "zsh", "fish"] {
+            let script = render_init(shell, "w", Some("ctrl-g")).unwrap();
+            assert!(!script.contains("{{"), "{}", shell);
+            assert!(script.contains("w ui < /dev/tty"), "{}", shell);
+        }
+        let zsh = render_init("zsh", "w", Some("alt-g")).unwrap();
+        assert!(zsh.contains("\nw() {\n"));
+        assert!(zsh.contains("compdef _wb w\n"));
+        assert!(zsh.contains("bindkey '^[g' __wb_pick"));
+        let fish = render_init("fish", "w", None).unwrap();
+        assert!(fish.contains("function __w_remove_directive_file --on-signal INT"));
+        assert!(!render_init("bash", "wb", None).unwrap().contains("bind -x"));
+        assert!(render_init("bash", "w;rm", None).is_err());
     }
 }
@@ -1,15 +1,28 @@
+use super::Key;
+
 pub const SHELL_INIT: &str = r#"# wb shell integration for zsh
 # Add to .zshrc: eval "$(wb init zsh)"
 
-wb() {
-  local result
-  result=$(command wb "$@")
-  local exit_code=$?
+{{cmd}}() {
+  # wb writes directives such as `__wb_cd:<dir>` to this file, leaving its
+  # stdout on the terminal
+  local directive_file exit_code line target
+  directive_file=$(mktemp "${TMPDIR:-/tmp}/wb.XXXXXX") || return 1
+  # Ctrl-C would otherwise abandon the function and leave the file behind
+  trap 'command rm -f "$directive_file"; trap - INT; return 130' INT
+  WB_DIRECTIVE_FILE="$directive_file" command wb "$@"
+  exit_code=$?
+  trap - INT
+
+  while IFS= read -r line; do
+    if [[ "$line" == __wb_cd:* ]]; then
+      target="${line#__wb_cd:}"
+    fi
+  done < "$directive_file"
+  command rm -f "$directive_file"
 
-  if [[ "$result" == __wb_cd:* ]]; then
-    builtin cd "${result#__wb_cd:}"
-  elif [[ -n "$result" ]]; then
-    echo "$result"
+  if [[ -n "$target" ]]; then
+    builtin cd "$target"
   fi
 
   return $exit_code
@@ -21,6 +34,19 @@ _wb() {
   subcmds=(
     'init:Initialize (shell integration or clone)'
     'list:List local branches'
+    'status:Show the state of every worktree'
+    'sync:Fetch and fast-forward branches behind upstream'
+    'clean:Delete merged and upstream-gone branches'
+    'land:Merge a branch into its base and remove it'
+    'restack:Rebase stacked branches onto their parents'
+    'session:Attach to a tmux/zellij session for a branch'
+    'open:Open a branch worktree in the editor'
+    'workspace:Write a .code-workspace with every worktree'
+    'ui:Browse branches in a full-screen dashboard'
+    'back:Go back to the previous worktree'
+    'recent:List or jump to recent worktrees'
+    'env:Print worktree ports and variables'
+    'foreach:Run a command in every worktree'
     'create:Create a branch with worktree'
     'delete:Delete branch(es) and worktrees'
     'rename:Rename a branch and move worktree'
@@ -28,24 +54,56 @@ _wb() {
   )
 
   if (( CURRENT == 2 )); then
-    _describe 'subcommand' subcmds
+    if [[ "${words[CURRENT]}" == -* ]]; then
+      local -a flags
+      flags=(
+        '-d:Delete branch(es) and worktrees'
+        '-D:Force delete branch(es) and worktrees'
+        '-m:Rename a branch and move worktree'
+        '-M:Force rename a branch and move worktree'
+        '-c:Copy a branch and create worktree'
+        '-C:Force copy a branch and create worktree'
+        '-l:List branches matching patterns'
+        '-v:List branches verbosely'
+        '-r:List remote-tracking branches'
+        '-a:List local and remote-tracking branches'
+        '--show-current:Print the current branch'
+      )
+      _describe 'flag' flags
+    else
+      _describe 'subcommand' subcmds
+    fi
     return
   fi
 
   case "${words[2]}" in
+    -d|-D|--delete|-m|-M|--move|-c|-C|--copy|-l|--list)
+      local -a branch_list
+      branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
+      _describe 'branch' branch_list
+      ;;
     init)
       if (( CURRENT == 3 )); then
         _alternative \
           'shells:shell:(zsh bash fish)' \
           'urls:url:_urls'
+      elif [[ $words[3] == (zsh|bash|fish) ]]; then
+        _arguments \
+          '--cmd[Name of the shell wrapper function]:name:' \
+          '--bind[Key that opens a branch picker (ctrl-<letter> or alt-<letter>)]:key:'
       elif (( CURRENT == 4 )); then
         _arguments '-d[Directory to clone into]:directory:_directories'
       fi
       ;;
     create)
-      local -a branch_list
-      branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
-      _describe 'branch' branch_list
+      _arguments \
+        '--warm=-[Clone build artifacts from another worktree]:branch:->branches' \
+        '*:branch:->branches'
+      if [[ $state == branches ]]; then
+        local -a branch_list
+        branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
+        _describe 'branch' branch_list
+      fi
       ;;
     delete)
       _arguments \
@@ -57,19 +115,104 @@ _wb() {
         _describe 'branch' branch_list
       fi
       ;;
-    rename|copy)
+    rename|copy|session|open)
       local -a branch_list
       branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
       _describe 'branch' branch_list
       ;;
+    clean)
+      _arguments \
+        '(-f --force)'{-f,--force}'[Also remove worktrees with local changes]' \
+        '(-n --dry-run)'{-n,--dry-run}'[Only show what would be deleted]' \
+        '(-y --yes)'{-y,--yes}'[Do not ask for confirmation]' \
+        '--no-fetch[Skip fetching remotes first]'
+      ;;
+    land)
+      _arguments \
+        '--base=[Base branch to land into]:base:->branches' \
+        '--strategy=[How to integrate the branch]:strategy:(ff merge rebase)' \
+        ':branch:->branches'
+      if [[ $state == branches ]]; then
+        local -a branch_list
+        branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
+        _describe 'branch' branch_list
+      fi
+      ;;
+    env)
+      _arguments \
+        '--write[Also rewrite the worktree env file]' \
+        ':branch:->branches'
+      if [[ $state == branches ]]; then
+        local -a branch_list
+        branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
+        _describe 'branch' branch_list
+      fi
+      ;;
+    recent)
+      _arguments \
+        '(-l --list)'{-l,--list}'[Only print the list]' \
+        ':index:'
+      ;;
+    foreach)
+      _arguments \
+        '--pattern=[Only worktrees whose branch matches glob]:glob:' \
+        '(-j --jobs)'{-j,--jobs}'[Number of parallel commands]:n:' \
+        '(-k --keep-going)'{-k,--keep-going}'[Keep going after a failure]' \
+        '*::command:_normal'
+      ;;
     list)
+      _arguments \
+        '(-a --all)'{-r,--remotes}'[List remote-tracking branches]' \
+        '(-r --remotes)'{-a,--all}'[List local and remote-tracking branches]' \
+        '*'{-v,--verbose}'[Show sha, tracking and subject]' \
+        '(--porcelain)--json[Print branches as JSON]' \
+        '(--json)--porcelain[Print branches in a stable format]' \
+        '--format=[Format each branch with a template]:template:' \
+        '--stack[Show stacked branches as a tree]' \
+        '--worktrees[List every worktree with its state]' \
+        '--sort=[Sort by for-each-ref key]:key:(refname -refname committerdate -committerdate)' \
+        '--merged=-[Only branches merged into commit]:commit:->branches' \
+        '--no-merged=-[Only branches not merged into commit]:commit:->branches' \
+        '--contains=-[Only branches containing commit]:commit:->branches' \
+        '--no-contains=-[Only branches not containing commit]:commit:->branches' \
+        '--landed=-[Only branches whose changes are in base]:base:->branches' \
+        '--mine[Only branches committed by you]' \
+        '*:pattern:->branches'
+      if [[ $state == branches ]]; then
+        local -a branch_list
+        branch_list=(${(f)"$(command git for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)"})
+        _describe 'branch' branch_list
+      fi
       ;;
   esac
 }
-compdef _wb wb
+compdef _wb {{cmd}}
 
 # Prompt helper
 wb_current_branch() {
   command git branch --show-current 2>/dev/null
 }
 "#;
+
+/// Key binding that opens `wb ui` and cds into the worktree picked there.
+pub const BIND: &str = r#"
+# Key binding: pick a branch in wb ui and cd into its worktree
+__wb_pick() {
+  {{cmd}} ui < /dev/tty
+  local precmd
+  for precmd in $precmd_functions; do
+    "$precmd"
+  done
+  zle reset-prompt
+}
+zle -N __wb_pick
+bindkey '{{key}}' __wb_pick
+"#;
+
+/// `ctrl-g` as `^G`, `alt-g` as `^[g`.
+pub fn key_notation(key: Key) -> String {
+    match key {
+        Key::Ctrl(c) => format!("^{}", c.to_ascii_uppercase()),
+        Key::Alt(c) => format!("^[{}", c),
+    }
+}
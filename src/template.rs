@@ -0,0 +1,147 @@
+use anyhow::{bail, Result};
+
+/// A field that can be substituted into a list format template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placeholder {
+    Branch,
+    Path,
+    Sha,
+    Upstream,
+    Ahead,
+    Behind,
+    Subject,
+    Current,
+    Dirty,
+}
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "branch" => Placeholder::Branch,
+            "path" => Placeholder::Path,
+            "sha" => Placeholder::Sha,
+            "upstream" => Placeholder::Upstream,
+            "ahead" => Placeholder::Ahead,
+            "behind" => Placeholder::Behind,
+            "subject" => Placeholder::Subject,
+            "current" => Placeholder::Current,
+            "dirty" => Placeholder::Dirty,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(Placeholder),
+}
+
+/// A parsed `wb list --format` template such as `{current} {branch}\t{path}`.
+///
+/// `{{` and `}}` produce literal braces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template, rejecting unknown or unterminated placeholders.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = input.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => bail!("fatal: unterminated placeholder '{{{}' in format", name),
+                        }
+                    }
+                    let Some(field) = Placeholder::from_name(&name) else {
+                        bail!("fatal: unknown placeholder '{{{}}}' in format", name);
+                    };
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(field));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template { segments })
+    }
+
+    /// Whether the template references the given placeholder.
+    pub fn uses(&self, placeholder: Placeholder) -> bool {
+        self.segments.contains(&Segment::Field(placeholder))
+    }
+
+    /// Render the template, asking `value` for each placeholder.
+    pub fn render(&self, value: impl Fn(Placeholder) -> String) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Field(field) => value(*field),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(input: &str) -> String {
+        Template::parse(input)
+            .unwrap()
+            .render(|p| format!("<{:?}>", p))
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        assert_eq!(render("{branch}\t{path}"), "<Branch>\t<Path>");
+        assert_eq!(
+            render("{current} {sha} {subject}"),
+            "<Current> <Sha> <Subject>"
+        );
+        assert_eq!(render("plain"), "plain");
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_eq!(render("{{{branch}}}"), "{<Branch>}");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(Template::parse("{nope}").is_err());
+        assert!(Template::parse("{branch").is_err());
+    }
+
+    #[test]
+    fn test_uses() {
+        let template = Template::parse("{branch} {dirty}").unwrap();
+        assert!(template.uses(Placeholder::Dirty));
+        assert!(!template.uses(Placeholder::Path));
+    }
+}
@@ -0,0 +1,55 @@
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::Command;
+
+use anyhow::Result;
+
+/// Give an interactive child the terminal as stdout. Shell wrappers without
+/// `WB_DIRECTIVE_FILE` capture wb's stdout to look for directives, so
+/// full-screen programs would otherwise draw into the capture.
+pub fn stdout_to_tty(cmd: &mut Command) {
+    if !std::io::stdout().is_terminal() {
+        if let Ok(tty) = OpenOptions::new().write(true).open("/dev/tty") {
+            cmd.stdout(tty);
+        }
+    }
+}
+
+/// Whether wb can ask the user questions (stdin is a terminal).
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Ask a yes/no question on stderr.
+pub fn confirm(question: &str) -> Result<bool> {
+    eprint!("{} [y/N] ", question);
+    Ok(matches!(read_answer()?.as_str(), "y" | "Y" | "yes"))
+}
+
+/// Ask the user to pick one of `options` by number on stderr.
+/// Returns `None` if they enter nothing or an invalid choice.
+pub fn choose(question: &str, options: &[String]) -> Result<Option<usize>> {
+    eprintln!("{}", question);
+    for (i, option) in options.iter().enumerate() {
+        eprintln!("  {:>2}) {}", i + 1, option);
+    }
+    eprint!("Select [1-{}]: ", options.len());
+    Ok(read_answer()?
+        .parse::<usize>()
+        .ok()
+        .filter(|n| (1..=options.len()).contains(n))
+        .map(|n| n - 1))
+}
+
+/// Report an error that does not stop the command as a warning.
+pub fn warn(e: &anyhow::Error) {
+    let msg = format!("{:#}", e);
+    eprintln!("warning: {}", msg.strip_prefix("fatal: ").unwrap_or(&msg));
+}
+
+fn read_answer() -> Result<String> {
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
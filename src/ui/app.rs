@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::config::WbConfig;
+use crate::git::{self, BranchFilter, Tracking};
+use crate::worktree::{self, WorktreeStatus};
+
+/// How many commits the preview shows.
+const LOG_LENGTH: &str = "10";
+
+/// A local branch as shown in the dashboard.
+pub struct BranchRow {
+    pub name: String,
+    pub worktree: Option<PathBuf>,
+    /// Working tree state, if the branch has a worktree.
+    pub status: Option<WorktreeStatus>,
+    pub upstream: Option<String>,
+    pub tracking: Tracking,
+    /// Relative date of the tip commit.
+    pub date: String,
+    /// The worktree wb was started from.
+    pub is_current: bool,
+}
+
+/// Details of a branch for the preview pane.
+pub struct Preview {
+    pub log: Vec<String>,
+    /// `git diff --stat` against the base branch, with the base's name.
+    pub diffstat: Option<(String, Vec<String>)>,
+    /// `git status --short` of the worktree.
+    pub changes: Vec<String>,
+}
+
+/// What a line of text typed at the prompt is for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputAction {
+    Create,
+    Rename,
+    Copy,
+}
+
+impl InputAction {
+    pub fn prompt(self, branch: &str) -> String {
+        match self {
+            InputAction::Create => format!("New branch from '{}': ", branch),
+            InputAction::Rename => format!("Rename '{}' to: ", branch),
+            InputAction::Copy => format!("Copy '{}' to: ", branch),
+        }
+    }
+}
+
+/// What keys currently do.
+pub enum Mode {
+    Normal,
+    /// Typing a branch name for `action` on the selected branch.
+    Input {
+        action: InputAction,
+        text: String,
+    },
+    /// Waiting for y/n before deleting `branch`.
+    Confirm {
+        branch: String,
+        force: bool,
+    },
+}
+
+/// A one-line report in the footer.
+pub struct Message {
+    pub text: String,
+    pub is_error: bool,
+}
+
+/// The dashboard state.
+pub struct App {
+    pub rows: Vec<BranchRow>,
+    pub selected: usize,
+    pub mode: Mode,
+    pub message: Option<Message>,
+    base: Option<String>,
+    previews: HashMap<String, Preview>,
+}
+
+impl App {
+    pub fn load() -> Result<Self> {
+        let base = WbConfig::load().and_then(|c| c.base_branch()).ok();
+        let mut app = App {
+            rows: Vec::new(),
+            selected: 0,
+            mode: Mode::Normal,
+            message: None,
+            base,
+            previews: HashMap::new(),
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    /// Re-read branches and worktrees, keeping the selected branch if it still exists.
+    pub fn reload(&mut self) -> Result<()> {
+        let selected = self.selected().map(|r| r.name.clone());
+
+        let worktrees = worktree::list_worktrees()?;
+        let current = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| worktree::find_worktree_for_path(&cwd).ok().flatten());
+
+        self.rows = git::list_branches(BranchFilter::default())?
+            .into_iter()
+            .map(|b| {
+                let wt = worktrees
+                    .iter()
+                    .find(|wt| wt.branch.as_deref() == Some(b.name.as_str()));
+                BranchRow {
+                    worktree: wt.map(|wt| wt.path.clone()),
+                    status: wt.and_then(|wt| worktree::status(&wt.path).ok()),
+                    is_current: wt
+                        .zip(current.as_ref())
+                        .is_some_and(|(wt, c)| wt.path == c.path),
+                    name: b.name,
+                    upstream: b.upstream,
+                    tracking: b.tracking,
+                    date: b.date,
+                }
+            })
+            .collect();
+        self.previews.clear();
+
+        match selected {
+            Some(name) => self.select(&name),
+            None => self.selected = 0,
+        }
+        self.move_by(0);
+        Ok(())
+    }
+
+    pub fn selected(&self) -> Option<&BranchRow> {
+        self.rows.get(self.selected)
+    }
+
+    /// Select the branch called `name`, if it is listed.
+    pub fn select(&mut self, name: &str) {
+        if let Some(i) = self.rows.iter().position(|r| r.name == name) {
+            self.selected = i;
+        }
+    }
+
+    /// Move the selection, stopping at either end.
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.rows.len().saturating_sub(1);
+    }
+
+    pub fn info(&mut self, text: String) {
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
+
+    pub fn error(&mut self, e: &anyhow::Error) {
+        let text = e.to_string();
+        self.message = Some(Message {
+            text: text.strip_prefix("fatal: ").unwrap_or(&text).to_string(),
+            is_error: true,
+        });
+    }
+
+    /// The preview of the selected branch, computed on first use.
+    pub fn preview(&mut self) -> Option<&Preview> {
+        let row = self.rows.get(self.selected)?;
+        if !self.previews.contains_key(&row.name) {
+            let preview = load_preview(&row.name, row.worktree.as_deref(), self.base.as_deref());
+            self.previews.insert(row.name.clone(), preview);
+        }
+        self.previews.get(&row.name)
+    }
+}
+
+fn load_preview(branch: &str, worktree: Option<&Path>, base: Option<&str>) -> Preview {
+    let rev = format!("refs/heads/{}", branch);
+    let log = git::run(&["log", "--oneline", "--no-decorate", "-n", LOG_LENGTH, &rev])
+        .map(|out| out.lines().map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let diffstat = base.filter(|b| *b != branch).map(|base| {
+        let range = format!("{}...{}", base, rev);
+        let stat = git::run(&["diff", "--stat", &range]).unwrap_or_default();
+        (
+            base.to_string(),
+            stat.lines().map(|l| l.trim().to_string()).collect(),
+        )
+    });
+
+    // `--branch` puts a header first, so the output trimming cannot eat the
+    // leading space of the first file's status
+    let changes = worktree
+        .and_then(|path| git::run_in(path, &["status", "--short", "--branch"]).ok())
+        .map(|out| out.lines().skip(1).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    Preview {
+        log,
+        diffstat,
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str) -> BranchRow {
+        BranchRow {
+            name: name.to_string(),
+            worktree: None,
+            status: None,
+            upstream: None,
+            tracking: Tracking::default(),
+            date: String::new(),
+            is_current: false,
+        }
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut app = App {
+            rows: vec![row("a"), row("b"), row("c")],
+            selected: 0,
+            mode: Mode::Normal,
+            message: None,
+            base: None,
+            previews: HashMap::new(),
+        };
+        app.move_by(-1);
+        assert_eq!(app.selected, 0);
+        app.move_by(5);
+        assert_eq!(app.selected, 2);
+        app.select("b");
+        assert_eq!(app.selected().unwrap().name, "b");
+        app.select("missing");
+        assert_eq!(app.selected, 1);
+    }
+}
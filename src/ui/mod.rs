@@ -0,0 +1,235 @@
+mod app;
+mod view;
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::cursor::{Hide, Show};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::Terminal;
+
+use crate::commands::{clean, copy, create, delete, rename};
+
+use app::{App, InputAction, Mode};
+
+/// Run the dashboard until the user quits. Returns the worktree to switch to, if any.
+///
+/// It draws on `/dev/tty` rather than stdout, which older shell wrappers capture.
+pub fn run() -> Result<Option<PathBuf>> {
+    let tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .map_err(|_| anyhow!("fatal: wb ui needs a terminal"))?;
+    let mut app = App::load()?;
+    let mut tui = Tui::enter(tty)?;
+
+    loop {
+        tui.terminal.draw(|frame| view::draw(frame, &mut app))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if let Some(exit) = handle_key(&mut app, &mut tui, key)? {
+            return Ok(exit);
+        }
+    }
+}
+
+/// The terminal in raw mode on the alternate screen; restored on drop.
+struct Tui {
+    terminal: Terminal<CrosstermBackend<File>>,
+    tty: File,
+}
+
+impl Tui {
+    fn enter(tty: File) -> Result<Self> {
+        let mut tui = Tui {
+            terminal: Terminal::new(CrosstermBackend::new(tty.try_clone()?))?,
+            tty,
+        };
+        tui.setup()?;
+        Ok(tui)
+    }
+
+    fn setup(&mut self) -> Result<()> {
+        enable_raw_mode()?;
+        execute!(self.tty, EnterAlternateScreen, Hide)?;
+        self.terminal.clear()?;
+        Ok(())
+    }
+
+    fn restore(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(self.tty, LeaveAlternateScreen, Show);
+    }
+
+    /// Leave the dashboard while `f` runs, so the messages, hooks and prompts
+    /// of the commands it calls reach the terminal.
+    fn suspend<T>(&mut self, f: impl FnOnce() -> T) -> Result<T> {
+        self.restore();
+        let result = f();
+        self.setup()?;
+        Ok(result)
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+/// Handle one key press. Returns `Some` when the dashboard should exit.
+fn handle_key(app: &mut App, tui: &mut Tui, key: KeyEvent) -> Result<Option<Option<PathBuf>>> {
+    match app.mode {
+        Mode::Normal => return handle_normal_key(app, tui, key),
+        Mode::Input {
+            action,
+            ref mut text,
+        } => match key.code {
+            KeyCode::Esc => app.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+            KeyCode::Enter => {
+                let name = text.trim().to_string();
+                app.mode = Mode::Normal;
+                if !name.is_empty() {
+                    run_input_action(app, tui, action, name)?;
+                }
+            }
+            _ => {}
+        },
+        Mode::Confirm { ref branch, force } => {
+            let branch = branch.clone();
+            app.mode = Mode::Normal;
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                let result = tui.suspend(|| delete::run(std::slice::from_ref(&branch), force))?;
+                finish(app, result.map(|_| format!("Deleted '{}'", branch)))?;
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn handle_normal_key(
+    app: &mut App,
+    tui: &mut Tui,
+    key: KeyEvent,
+) -> Result<Option<Option<PathBuf>>> {
+    app.message = None;
+    let branch = app.selected().map(|r| r.name.clone());
+
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(None)),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return Ok(Some(None))
+        }
+        KeyCode::Down | KeyCode::Char('j') => app.move_by(1),
+        KeyCode::Up | KeyCode::Char('k') => app.move_by(-1),
+        KeyCode::PageDown => app.move_by(10),
+        KeyCode::PageUp => app.move_by(-10),
+        KeyCode::Home | KeyCode::Char('g') => app.selected = 0,
+        KeyCode::End | KeyCode::Char('G') => app.select_last(),
+        KeyCode::F(5) => app.reload()?,
+        KeyCode::Char('C') => {
+            tui.suspend(|| {
+                if let Err(e) = clean::run(false, false, false, true) {
+                    eprintln!("{}", e);
+                }
+                eprint!("Press Enter to return to wb ui ");
+                let _ = io::stdin().lock().read_line(&mut String::new());
+            })?;
+            app.reload()?;
+        }
+        _ => {}
+    }
+
+    let Some(branch) = branch else {
+        return Ok(None);
+    };
+    match key.code {
+        KeyCode::Enter => match app.selected().and_then(|r| r.worktree.clone()) {
+            Some(path) => return Ok(Some(Some(path))),
+            None => match tui.suspend(|| create::ensure(&branch, None, None))? {
+                Ok(path) => return Ok(Some(Some(path))),
+                Err(e) => app.error(&e),
+            },
+        },
+        KeyCode::Char('n') => {
+            app.mode = Mode::Input {
+                action: InputAction::Create,
+                text: String::new(),
+            }
+        }
+        KeyCode::Char('r') => {
+            app.mode = Mode::Input {
+                action: InputAction::Rename,
+                text: branch,
+            }
+        }
+        KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.mode = Mode::Input {
+                action: InputAction::Copy,
+                text: String::new(),
+            }
+        }
+        KeyCode::Char('d') => {
+            app.mode = Mode::Confirm {
+                branch,
+                force: false,
+            }
+        }
+        KeyCode::Char('D') => {
+            app.mode = Mode::Confirm {
+                branch,
+                force: true,
+            }
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Create, rename or copy the selected branch to `name`, then select the result.
+fn run_input_action(app: &mut App, tui: &mut Tui, action: InputAction, name: String) -> Result<()> {
+    let Some(branch) = app.selected().map(|r| r.name.clone()) else {
+        return Ok(());
+    };
+    let result = tui.suspend(|| match action {
+        InputAction::Create => create::ensure(&name, Some(&branch), None)
+            .map(|_| format!("Created '{}' from '{}'", name, branch)),
+        InputAction::Rename => rename::run(&name, Some(&branch), false)
+            .map(|_| format!("Renamed '{}' to '{}'", branch, name)),
+        InputAction::Copy => {
+            copy::copy(&branch, &name, false).map(|_| format!("Copied '{}' to '{}'", branch, name))
+        }
+    })?;
+    let ok = result.is_ok();
+    finish(app, result)?;
+    if ok {
+        app.select(&name);
+    }
+    Ok(())
+}
+
+/// Reload after an action and report how it went.
+fn finish(app: &mut App, result: Result<String>) -> Result<()> {
+    app.reload()?;
+    match result {
+        Ok(text) => app.info(text),
+        Err(e) => app.error(&e),
+    }
+    Ok(())
+}
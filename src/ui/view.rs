@@ -0,0 +1,195 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+
+use crate::git::Tracking;
+
+use super::app::{App, BranchRow, Mode};
+
+const HINTS: &str = "enter switch  n new  r rename  c copy  d delete  D force delete  \
+                     C clean  F5 refresh  q quit";
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [list, preview] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main);
+
+    draw_branches(frame, app, list);
+    draw_preview(frame, app, preview);
+    draw_footer(frame, app, footer);
+}
+
+fn draw_branches(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.rows.iter().map(|row| {
+        let style = if row.worktree.is_some() {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let marker = if row.is_current { "*" } else { " " };
+        let (changes, changes_color) = format_changes(row);
+        Row::new(vec![
+            Cell::from(marker).green(),
+            Cell::from(row.name.as_str()),
+            Cell::from(changes).fg(changes_color),
+            Cell::from(format_upstream(row.upstream.as_deref(), &row.tracking)),
+            Cell::from(row.date.as_str()),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(14),
+        ],
+    )
+    .header(Row::new(["", "BRANCH", "CHANGES", "UPSTREAM", "UPDATED"]).bold())
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+    .block(Block::bordered().title(" wb "));
+
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_preview(frame: &mut Frame, app: &mut App, area: Rect) {
+    let Some(row) = app.selected() else {
+        frame.render_widget(Block::bordered(), area);
+        return;
+    };
+    let title = format!(" {} ", row.name);
+    let mut lines = vec![match row.worktree {
+        Some(ref path) => Line::from(path.display().to_string()).dim(),
+        None => Line::from("no worktree (enter creates one)").dim(),
+    }];
+
+    if let Some(preview) = app.preview() {
+        section(&mut lines, "Recent commits", &preview.log);
+        if let Some((ref base, ref stat)) = preview.diffstat {
+            section(&mut lines, &format!("Changes since {}", base), stat);
+        }
+        section(&mut lines, "Uncommitted changes", &preview.changes);
+    }
+
+    let paragraph = Paragraph::new(lines).block(Block::bordered().title(title));
+    frame.render_widget(paragraph, area);
+}
+
+/// Append a titled block of lines, skipping it when there is nothing to show.
+fn section(lines: &mut Vec<Line<'static>>, title: &str, body: &[String]) {
+    if body.is_empty() {
+        return;
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(title.to_string()).bold());
+    lines.extend(body.iter().map(|l| Line::from(l.clone())));
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let branch = app.selected().map(|r| r.name.as_str()).unwrap_or_default();
+    let line = match app.mode {
+        Mode::Input { action, ref text } => Line::from(vec![
+            Span::raw(action.prompt(branch)),
+            Span::raw(text.clone()),
+            Span::raw("█"),
+        ]),
+        Mode::Confirm { ref branch, force } => {
+            let verb = if force { "Force delete" } else { "Delete" };
+            Line::from(format!("{} '{}' and its worktree? [y/N]", verb, branch)).yellow()
+        }
+        Mode::Normal => match app.message {
+            Some(ref m) if m.is_error => Line::from(m.text.clone()).red(),
+            Some(ref m) => Line::from(m.text.clone()).green(),
+            None => Line::from(HINTS).dim(),
+        },
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Worktree state as `+staged ~modified ?untracked !conflicted`, plus any operation.
+fn format_changes(row: &BranchRow) -> (String, Color) {
+    let Some(ref status) = row.status else {
+        return (String::new(), Color::Reset);
+    };
+    let mut parts = Vec::new();
+    let counts = [
+        ('+', status.staged),
+        ('~', status.modified),
+        ('?', status.untracked),
+        ('!', status.conflicted),
+    ];
+    for (sign, n) in counts {
+        if n > 0 {
+            parts.push(format!("{}{}", sign, n));
+        }
+    }
+    if let Some(op) = status.operation {
+        parts.push(op.name().to_uppercase());
+    }
+    if parts.is_empty() {
+        return ("clean".to_string(), Color::Green);
+    }
+    // Conflicts and half-finished operations need attention first
+    let color = if status.conflicted > 0 || status.operation.is_some() {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    (parts.join(" "), color)
+}
+
+/// Ahead/behind counts against the upstream, `=` when in sync, `gone` when deleted.
+fn format_upstream(upstream: Option<&str>, tracking: &Tracking) -> String {
+    if tracking.gone {
+        return "gone".to_string();
+    }
+    if upstream.is_none() {
+        return String::new();
+    }
+    let mut parts = Vec::new();
+    if tracking.ahead > 0 {
+        parts.push(format!("↑{}", tracking.ahead));
+    }
+    if tracking.behind > 0 {
+        parts.push(format!("↓{}", tracking.behind));
+    }
+    if parts.is_empty() {
+        "=".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_upstream() {
+        let tracking = |ahead, behind, gone| Tracking {
+            ahead,
+            behind,
+            gone,
+        };
+        assert_eq!(format_upstream(None, &tracking(0, 0, false)), "");
+        assert_eq!(
+            format_upstream(Some("origin/a"), &tracking(0, 0, false)),
+            "="
+        );
+        assert_eq!(
+            format_upstream(Some("origin/a"), &tracking(2, 1, false)),
+            "↑2 ↓1"
+        );
+        assert_eq!(
+            format_upstream(Some("origin/a"), &tracking(0, 0, true)),
+            "gone"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::config::WbConfig;
+use crate::git;
+use crate::worktree;
+
+/// The workspace file: `wb.workspaceFile` (relative to the project root),
+/// else `<root>/<project>.code-workspace`.
+pub fn file_path(config: &WbConfig) -> Result<PathBuf> {
+    let root = git::find_root_dir()?;
+    Ok(match config.workspace_file {
+        Some(ref file) => root.join(file),
+        None => {
+            let project = root
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "wb".to_string());
+            root.join(format!("{}.code-workspace", project))
+        }
+    })
+}
+
+/// Write the workspace file with every worktree as a root folder (sorted by name), keeping
+/// any other settings already in it. Returns the file's path.
+pub fn write(config: &WbConfig) -> Result<PathBuf> {
+    let file = file_path(config)?;
+    let dir = file.parent().unwrap_or(Path::new("."));
+
+    let mut folders: Vec<(String, String)> = worktree::list_worktrees()?
+        .into_iter()
+        .filter(|wt| !wt.is_bare)
+        .map(|wt| {
+            let name = wt.branch.clone().unwrap_or_else(|| {
+                wt.path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+            let path = wt.path.strip_prefix(dir).unwrap_or(&wt.path);
+            (name, path.display().to_string())
+        })
+        .collect();
+    folders.sort();
+
+    let existing = fs::read_to_string(&file).ok();
+    let contents = render(existing.as_deref(), &folders)
+        .map_err(|e| anyhow!("fatal: cannot update '{}': {}", file.display(), e))?;
+    fs::write(&file, contents)?;
+    Ok(file)
+}
+
+/// Regenerate the workspace file after worktrees changed, if one was generated before.
+pub fn refresh() {
+    let Ok(config) = WbConfig::load() else {
+        return;
+    };
+    if file_path(&config).is_ok_and(|f| f.is_file()) {
+        if let Err(e) = write(&config) {
+            let msg = e.to_string();
+            eprintln!("warning: {}", msg.strip_prefix("fatal: ").unwrap_or(&msg));
+        }
+    }
+}
+
+/// Replace the `folders` of an existing workspace (or start a new one).
+fn render(existing: Option<&str>, folders: &[(String, String)]) -> Result<String> {
+    let mut workspace = match existing {
+        Some(text) if !text.trim().is_empty() => {
+            serde_json::from_str::<Map<String, Value>>(text)
+                .context("not a JSON object (comments are not supported)")?
+        }
+        _ => Map::new(),
+    };
+
+    let folders = folders
+        .iter()
+        .map(|(name, path)| json!({ "name": name, "path": path }))
+        .collect();
+    workspace.insert("folders".to_string(), Value::Array(folders));
+    workspace
+        .entry("settings")
+        .or_insert_with(|| Value::Object(Map::new()));
+
+    Ok(serde_json::to_string_pretty(&workspace)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_keeps_settings() {
+        let folders = vec![("main".to_string(), "main".to_string())];
+        let fresh = render(None, &folders).unwrap();
+        assert_eq!(
+            fresh,
+            "{\n  \"folders\": [\n    {\n      \"name\": \"main\",\n      \"path\": \"main\"\n    }\n  ],\n  \"settings\": {}\n}\n"
+        );
+
+        let existing = r#"{"folders": [{"path": "old"}], "settings": {"a": 1}}"#;
+        let updated: Value =
+            serde_json::from_str(&render(Some(existing), &folders).unwrap()).unwrap();
+        assert_eq!(updated["folders"][0]["path"], "main");
+        assert_eq!(updated["settings"]["a"], 1);
+
+        assert!(render(Some("// comment\n{}"), &folders).is_err());
+    }
+}
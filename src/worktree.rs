@@ -12,6 +12,10 @@ pub struct WorktreeInfo {
     pub branch: Option<String>,
     pub is_bare: bool,
     pub is_detached: bool,
+    /// Lock reason (empty if locked without one); `None` if not locked.
+    pub locked: Option<String>,
+    /// Why git considers the worktree prunable; `None` if it is not.
+    pub prunable: Option<String>,
 }
 
 /// Parse `git worktree list --porcelain` output into structured data.
@@ -31,6 +35,8 @@ pub fn list_worktrees() -> Result<Vec<WorktreeInfo>> {
                 branch: None,
                 is_bare: false,
                 is_detached: false,
+                locked: None,
+                prunable: None,
             });
         } else if let Some(stripped) = line.strip_prefix("HEAD ") {
             if let Some(ref mut wt) = current {
@@ -50,6 +56,14 @@ pub fn list_worktrees() -> Result<Vec<WorktreeInfo>> {
             if let Some(ref mut wt) = current {
                 wt.is_detached = true;
             }
+        } else if line == "locked" || line.starts_with("locked ") {
+            if let Some(ref mut wt) = current {
+                wt.locked = Some(line["locked".len()..].trim_start().to_string());
+            }
+        } else if line == "prunable" || line.starts_with("prunable ") {
+            if let Some(ref mut wt) = current {
+                wt.prunable = Some(line["prunable".len()..].trim_start().to_string());
+            }
         }
     }
 
@@ -141,9 +155,181 @@ pub fn find_worktree_for_path(path: &Path) -> Result<Option<WorktreeInfo>> {
     Ok(best)
 }
 
+/// An operation left in progress in a worktree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Rebase,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Operation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Rebase => "rebase",
+            Operation::Merge => "merge",
+            Operation::CherryPick => "cherry-pick",
+            Operation::Revert => "revert",
+            Operation::Bisect => "bisect",
+        }
+    }
+}
+
+/// Working tree state of a single worktree, from `git status --porcelain=v2`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorktreeStatus {
+    pub staged: usize,
+    pub modified: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub operation: Option<Operation>,
+}
+
+impl WorktreeStatus {
+    /// No staged, modified, untracked or conflicted files.
+    pub fn is_clean(&self) -> bool {
+        self.staged == 0 && self.modified == 0 && self.untracked == 0 && self.conflicted == 0
+    }
+}
+
+/// Collect the working tree state of the worktree at `path`.
+pub fn status(path: &Path) -> Result<WorktreeStatus> {
+    let output = git::run_in(path, &["status", "--porcelain=v2", "--branch"])?;
+    let mut status = parse_status(&output);
+
+    let git_dir = PathBuf::from(git::run_in(path, &["rev-parse", "--absolute-git-dir"])?);
+    status.operation = detect_operation(&git_dir);
+
+    Ok(status)
+}
+
+fn parse_status(output: &str) -> WorktreeStatus {
+    let mut status = WorktreeStatus::default();
+
+    for line in output.lines() {
+        if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            status.upstream = Some(upstream.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    status.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    status.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") {
+            let xy = line.as_bytes().get(2..4).unwrap_or(b"..");
+            if xy[0] != b'.' {
+                status.staged += 1;
+            }
+            if xy[1] != b'.' {
+                status.modified += 1;
+            }
+        } else if line.starts_with("u ") {
+            status.conflicted += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+
+    status
+}
+
+/// Detect an in-progress operation from the state files in a worktree's git dir.
+fn detect_operation(git_dir: &Path) -> Option<Operation> {
+    if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+        Some(Operation::Rebase)
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        Some(Operation::Merge)
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        Some(Operation::CherryPick)
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        Some(Operation::Revert)
+    } else if git_dir.join("BISECT_LOG").exists() {
+        Some(Operation::Bisect)
+    } else {
+        None
+    }
+}
+
+/// Check whether a worktree has uncommitted changes or untracked files.
+pub fn is_dirty(path: &Path) -> bool {
+    git::run_in(path, &["status", "--porcelain"]).is_ok_and(|out| !out.is_empty())
+}
+
+/// Find directories under `dir` that are not registered worktrees.
+///
+/// Hidden entries (such as `.bare`) are ignored, as are directories that
+/// contain a worktree (e.g. `feature/` with the nested naming convention).
+pub fn find_stray_dirs(dir: &Path, worktrees: &[WorktreeInfo]) -> Result<Vec<PathBuf>> {
+    let known: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|wt| wt.path.canonicalize().unwrap_or_else(|_| wt.path.clone()))
+        .collect();
+
+    let mut stray = Vec::new();
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !known.iter().any(|wt| wt.starts_with(&canonical)) {
+            stray.push(path);
+        }
+    }
+
+    stray.sort();
+    Ok(stray)
+}
+
 /// Prune worktrees (clean up stale entries).
 #[allow(dead_code)]
 pub fn prune() -> Result<()> {
     git::run(&["worktree", "prune"])?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let output = "\
+# branch.oid 1234
+# branch.head feature
+# branch.upstream origin/feature
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 a b staged.txt
+1 .M N... 100644 100644 100644 a b modified.txt
+1 MM N... 100644 100644 100644 a b both.txt
+2 R. N... 100644 100644 100644 a b R100 new.txt\told.txt
+u UU N... 100644 100644 100644 100644 a b c conflict.txt
+? untracked.txt";
+
+        let status = parse_status(output);
+        assert_eq!(status.staged, 3);
+        assert_eq!(status.modified, 2);
+        assert_eq!(status.conflicted, 1);
+        assert_eq!(status.untracked, 1);
+        assert_eq!(status.upstream.as_deref(), Some("origin/feature"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert!(!status.is_clean());
+    }
+
+    #[test]
+    fn test_parse_clean_status() {
+        let status = parse_status("# branch.oid 1234\n# branch.head main");
+        assert!(status.is_clean());
+        assert_eq!(status.upstream, None);
+    }
+}
@@ -0,0 +1,146 @@
+use assert_cmd::assert::OutputAssertExt;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, git, setup_project, wb};
+
+#[test]
+fn bare_name_creates_branch_and_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    let output = wb(&main_wt).arg("feature/x").output().unwrap();
+    assert!(output.status.success());
+
+    let target = cd_target(&output.stdout).expect("expected a cd directive");
+    assert_eq!(
+        target.canonicalize().unwrap(),
+        root.join("feature--x").canonicalize().unwrap()
+    );
+    assert_eq!(git(&target, &["branch", "--show-current"]), "feature/x");
+}
+
+#[test]
+fn bare_name_with_start_point() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    let main_head = git(&main_wt, &["rev-parse", "HEAD"]);
+    common::commit_file(&main_wt, "second.txt", "2\n", "second");
+
+    let output = wb(&main_wt).args(["old", &main_head]).output().unwrap();
+    assert!(output.status.success());
+    let target = cd_target(&output.stdout).unwrap();
+    assert_eq!(git(&target, &["rev-parse", "HEAD"]), main_head);
+}
+
+#[test]
+fn delete_flags() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("topic").assert().success();
+    let topic_wt = root.join("topic");
+    common::commit_file(&topic_wt, "topic.txt", "t\n", "topic work");
+
+    wb(&main_wt)
+        .args(["-d", "topic"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("wb -D topic"));
+    assert!(
+        topic_wt.is_dir(),
+        "failed safe delete must keep the worktree"
+    );
+
+    wb(&main_wt)
+        .args(["-D", "topic"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Deleted branch topic"));
+    assert!(!topic_wt.exists());
+}
+
+#[test]
+fn move_flag_uses_git_argument_order() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("old-name").assert().success();
+    wb(&main_wt)
+        .args(["-m", "old-name", "new-name"])
+        .assert()
+        .success();
+
+    assert!(!root.join("old-name").exists());
+    assert_eq!(
+        git(&root.join("new-name"), &["branch", "--show-current"]),
+        "new-name"
+    );
+}
+
+#[test]
+fn copy_flag_from_current_branch() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    let output = wb(&main_wt).args(["-c", "copied"]).output().unwrap();
+    assert!(output.status.success());
+    assert!(root.join("copied").is_dir());
+    assert_eq!(
+        git(&main_wt, &["rev-parse", "HEAD"]),
+        git(&root.join("copied"), &["rev-parse", "HEAD"])
+    );
+}
+
+#[test]
+fn show_current_and_list_patterns() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("feature/a").assert().success();
+    wb(&main_wt).arg("bugfix/b").assert().success();
+
+    wb(&root.join("feature--a"))
+        .arg("--show-current")
+        .assert()
+        .success()
+        .stdout("feature/a\n");
+
+    wb(&main_wt)
+        .args(["-l", "feature/*"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/a"))
+        .stdout(predicate::str::contains("bugfix/b").not());
+}
+
+#[test]
+fn conflicting_flags_are_rejected() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).args(["-d", "-m", "x"]).assert().failure();
+    wb(&main_wt)
+        .args(["-m", "a", "b", "c"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("too many arguments"));
+
+    wb(&main_wt).arg("x").assert().success();
+    for args in [
+        &["-v", "-d", "x"][..],
+        &["-v", "-m", "a", "b"],
+        &["-vv", "-c", "x"],
+    ] {
+        wb(&main_wt)
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "--verbose can only be used when listing branches",
+            ));
+    }
+    assert!(!git(&main_wt, &["branch", "--list", "x"]).is_empty());
+}
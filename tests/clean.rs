@@ -0,0 +1,95 @@
+use std::fs;
+
+use assert_cmd::assert::OutputAssertExt;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_clone, setup_project, wb};
+
+#[test]
+fn clean_removes_merged_and_gone_branches() {
+    let tmp = TempDir::new().unwrap();
+    let (upstream, main_wt) = setup_clone(tmp.path());
+    let project = main_wt.parent().unwrap().to_path_buf();
+
+    // merged: fully contained in the base; has a worktree to remove
+    wb(&main_wt).arg("merged").output().unwrap();
+
+    // gone: tracked a remote branch that has since been deleted
+    git(&upstream, &["branch", "gone"]);
+    git(&main_wt, &["fetch", "origin"]);
+    wb(&main_wt).arg("gone").output().unwrap();
+    commit_file(
+        &project.join("gone"),
+        "gone.txt",
+        "g\n",
+        "squashed upstream",
+    );
+    git(&upstream, &["branch", "-D", "gone"]);
+
+    // dirty: merged, but its worktree has local changes
+    wb(&main_wt).arg("dirty").output().unwrap();
+    fs::write(project.join("dirty").join("file.txt"), "edit\n").unwrap();
+
+    // active: unmerged work, must be kept
+    wb(&main_wt).arg("active").output().unwrap();
+    commit_file(&project.join("active"), "a.txt", "a\n", "work");
+
+    wb(&main_wt)
+        .args(["clean", "--dry-run"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("upstream gone"))
+        .stderr(predicate::str::contains(
+            "(skipped: worktree has local changes)",
+        ));
+    assert!(project.join("merged").is_dir(), "dry run must not delete");
+
+    wb(&main_wt)
+        .args(["clean", "--yes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Deleted branch merged"))
+        .stderr(predicate::str::contains("Deleted branch gone"));
+
+    let branches = git(&main_wt, &["branch", "--format=%(refname:short)"]);
+    assert!(!branches.contains("merged"));
+    assert!(!branches.lines().any(|b| b == "gone"));
+    assert!(branches.contains("dirty"));
+    assert!(branches.contains("active"));
+    assert!(!project.join("merged").exists());
+    assert!(project.join("dirty").is_dir());
+
+    wb(&main_wt)
+        .args(["clean", "--yes", "--force", "--no-fetch"])
+        .assert()
+        .success();
+    assert!(!project.join("dirty").exists());
+}
+
+#[test]
+fn clean_uses_configured_base_and_requires_confirmation() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("release").output().unwrap();
+    let release = main_wt.parent().unwrap().join("release");
+    commit_file(&release, "r.txt", "r\n", "release work");
+    git(&release, &["branch", "fix", "HEAD"]);
+    git(&main_wt, &["config", "wb.base", "release"]);
+
+    // stdin is not a terminal here, so deleting needs --yes
+    wb(&main_wt)
+        .arg("clean")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("merged into release"))
+        .stderr(predicate::str::contains("--yes"));
+
+    wb(&main_wt).args(["clean", "-y"]).assert().success();
+    let branches = git(&main_wt, &["branch", "--format=%(refname:short)"]);
+    assert!(!branches.lines().any(|b| b == "fix"));
+    assert!(branches.lines().any(|b| b == "release"));
+}
@@ -0,0 +1,84 @@
+#![allow(deprecated, dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use assert_cmd::cargo::CommandCargoExt;
+
+/// Run a git command in `dir`, panicking on failure. Returns trimmed stdout.
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to execute git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commit a file with the given contents in `dir`.
+pub fn commit_file(dir: &Path, name: &str, contents: &str, message: &str) {
+    fs::write(dir.join(name), contents).unwrap();
+    git(dir, &["add", name]);
+    git(dir, &["commit", "-m", message]);
+}
+
+/// Create a repo with one commit and convert it to wb's bare-repo layout.
+/// Returns the project root and the worktree of the default branch.
+pub fn setup_project(root: &Path) -> (PathBuf, PathBuf) {
+    git(root, &["init"]);
+    git(root, &["config", "user.email", "test@test.com"]);
+    git(root, &["config", "user.name", "Test"]);
+    commit_file(root, "file.txt", "hello\n", "init");
+    let branch = git(root, &["symbolic-ref", "--short", "HEAD"]);
+
+    let output = wb(root).arg("init").output().unwrap();
+    assert!(output.status.success(), "wb init failed");
+
+    (root.to_path_buf(), root.join(branch))
+}
+
+/// A `wb` command running in `dir`.
+pub fn wb(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("wb").unwrap();
+    cmd.current_dir(dir);
+    cmd
+}
+
+/// Extract the `__wb_cd:` target from command stdout.
+pub fn cd_target(stdout: &[u8]) -> Option<PathBuf> {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("__wb_cd:").map(PathBuf::from))
+}
+
+/// Create an upstream repo at `root/upstream` and clone it with `wb init <url>`
+/// into `root/project`. Returns the upstream repo and the default branch's worktree.
+pub fn setup_clone(root: &Path) -> (PathBuf, PathBuf) {
+    let upstream = root.join("upstream");
+    fs::create_dir(&upstream).unwrap();
+    git(&upstream, &["init"]);
+    git(&upstream, &["config", "user.email", "test@test.com"]);
+    git(&upstream, &["config", "user.name", "Test"]);
+    commit_file(&upstream, "file.txt", "hello\n", "init");
+    let branch = git(&upstream, &["symbolic-ref", "--short", "HEAD"]);
+
+    let project = root.join("project");
+    let output = wb(root)
+        .args(["init", &upstream.to_string_lossy(), "-d"])
+        .arg(&project)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "wb init <url> failed");
+
+    let main_wt = project.join(branch);
+    git(&main_wt, &["config", "user.email", "test@test.com"]);
+    git(&main_wt, &["config", "user.name", "Test"]);
+    (upstream, main_wt)
+}
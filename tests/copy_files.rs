@@ -0,0 +1,106 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, commit_file, git, setup_project, wb};
+
+/// Project whose default worktree has ignored `.env`, `.vscode/settings.json`
+/// and `config/local.yml`, plus an untracked but not ignored `notes.txt`.
+fn setup_with_local_files(root: &std::path::Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let (root, main_wt) = setup_project(root);
+    commit_file(
+        &main_wt,
+        ".gitignore",
+        ".env\n.vscode/\nconfig/local.yml\n",
+        "ignore local files",
+    );
+    fs::write(main_wt.join(".env"), "SECRET=main\n").unwrap();
+    fs::create_dir_all(main_wt.join(".vscode")).unwrap();
+    fs::write(main_wt.join(".vscode/settings.json"), "{}\n").unwrap();
+    fs::create_dir_all(main_wt.join("config")).unwrap();
+    fs::write(main_wt.join("config/local.yml"), "local: true\n").unwrap();
+    fs::write(main_wt.join("notes.txt"), "mine\n").unwrap();
+
+    git(
+        &main_wt,
+        &["config", "wb.copyFiles", ".env .vscode/settings.json"],
+    );
+    git(
+        &main_wt,
+        &["config", "--add", "wb.copyFiles", "config/*.yml notes.txt"],
+    );
+    (root, main_wt)
+}
+
+#[test]
+fn create_copies_ignored_files_from_current_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_local_files(tmp.path());
+
+    let output = wb(&main_wt).arg("feat").output().unwrap();
+    assert!(output.status.success());
+
+    let feat_wt = root.join("feat");
+    assert_eq!(
+        fs::read_to_string(feat_wt.join(".env")).unwrap(),
+        "SECRET=main\n"
+    );
+    assert!(feat_wt.join(".vscode/settings.json").is_file());
+    assert!(feat_wt.join("config/local.yml").is_file());
+    assert!(
+        !feat_wt.join("notes.txt").exists(),
+        "untracked files that are not ignored must not be copied"
+    );
+}
+
+#[test]
+fn create_and_copy_take_files_from_the_source_branch_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_local_files(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    fs::write(feat_wt.join(".env"), "SECRET=feat\n").unwrap();
+
+    wb(&main_wt)
+        .args(["create", "child", "feat"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(root.join("child").join(".env")).unwrap(),
+        "SECRET=feat\n"
+    );
+
+    let output = wb(&main_wt)
+        .args(["copy", "twin", "feat"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(root.join("twin").join(".env")).unwrap(),
+        "SECRET=feat\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_file_copy_still_finishes_the_copy() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_local_files(tmp.path());
+    // A socket cannot be copied
+    commit_file(&main_wt, ".gitignore", ".env\napp.sock\n", "ignore socket");
+    let _socket = std::os::unix::net::UnixListener::bind(main_wt.join("app.sock")).unwrap();
+    git(&main_wt, &["config", "--add", "wb.copyFiles", "app.sock"]);
+    git(&main_wt, &["config", "wb.hook.postCopy", "touch copied"]);
+
+    let output = wb(&main_wt).args(["-c", "twin"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: "));
+    assert_eq!(cd_target(&output.stdout), Some(root.join("twin")));
+    assert!(root.join("twin").join("copied").is_file());
+}
@@ -0,0 +1,128 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{git, setup_project, wb};
+
+#[test]
+fn create_writes_env_file_with_unique_ports() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.envFile", ".env"]);
+    git(&main_wt, &["config", "wb.portBase", "4000"]);
+
+    wb(&main_wt).arg("one").output().unwrap();
+    wb(&main_wt).arg("feature/two").output().unwrap();
+
+    let one = fs::read_to_string(root.join("one/.env")).unwrap();
+    let project = root.file_name().unwrap().to_string_lossy().to_lowercase();
+    assert!(one.contains("WB_BRANCH=one\n"), "{}", one);
+    assert!(one.contains("WB_PORT=4000\n"), "{}", one);
+    assert!(one.contains("WB_PORT_RANGE=4000-4009\n"), "{}", one);
+    assert!(
+        one.contains(&format!(
+            "WB_DB_NAME={}_one\n",
+            project.replace(['.', '-'], "_")
+        )),
+        "{}",
+        one
+    );
+
+    let two = fs::read_to_string(root.join("feature--two/.env")).unwrap();
+    assert!(two.contains("WB_PORT=4010\n"), "{}", two);
+    assert_eq!(
+        git(&main_wt, &["config", "branch.feature/two.wbPort"]),
+        "4010"
+    );
+}
+
+#[test]
+fn env_command_prints_and_rewrites_without_touching_user_lines() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    fs::write(feat_wt.join(".envrc"), "use flake\n").unwrap();
+
+    let output = wb(&feat_wt).arg("env").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("WB_BRANCH=feat\n"), "{}", stdout);
+    assert!(stdout.contains("WB_PORT=3000\n"), "{}", stdout);
+
+    git(&main_wt, &["config", "wb.envFile", ".envrc"]);
+    let output = wb(&feat_wt).args(["env", "--write"]).output().unwrap();
+    assert!(output.status.success());
+    wb(&feat_wt).args(["env", "--write"]).output().unwrap();
+    let envrc = fs::read_to_string(feat_wt.join(".envrc")).unwrap();
+    assert!(envrc.starts_with("use flake\n# >>> wb >>>\n"), "{}", envrc);
+    assert!(envrc.contains("export WB_PORT=3000\n"), "{}", envrc);
+    assert_eq!(envrc.matches("# >>> wb >>>").count(), 1);
+}
+
+#[test]
+fn rename_keeps_port_and_copy_gets_its_own() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.envFile", ".env"]);
+    wb(&main_wt).arg("old").output().unwrap();
+
+    wb(&main_wt)
+        .args(["rename", "new", "old"])
+        .output()
+        .unwrap();
+    let new = fs::read_to_string(root.join("new/.env")).unwrap();
+    assert!(new.contains("WB_BRANCH=new\n"), "{}", new);
+    assert!(new.contains("WB_PORT=3000\n"), "{}", new);
+
+    wb(&main_wt).args(["copy", "twin", "new"]).output().unwrap();
+    let twin = fs::read_to_string(root.join("twin/.env")).unwrap();
+    assert!(twin.contains("WB_PORT=3010\n"), "{}", twin);
+}
+
+#[test]
+fn failing_env_file_still_finishes_the_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.envFile", "missing/.env"]);
+    git(&main_wt, &["config", "wb.hook.postCreate", "touch created"]);
+
+    let output = wb(&main_wt).arg("feat").output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: "));
+    assert!(root.join("feat").join("created").is_file());
+}
+
+#[test]
+fn failing_env_file_still_finishes_copy_and_rename() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    git(&main_wt, &["config", "wb.envFile", "missing/.env"]);
+    git(&main_wt, &["config", "wb.hook.postCopy", "touch copied"]);
+    git(&main_wt, &["config", "wb.hook.postRename", "touch renamed"]);
+
+    let output = wb(&main_wt)
+        .args(["copy", "twin", &main_branch])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let missing = root.join("twin").join("missing/.env");
+    assert!(
+        stderr.contains(&format!("warning: cannot write '{}'", missing.display())),
+        "{}",
+        stderr
+    );
+    assert!(root.join("twin").join("copied").is_file());
+
+    let output = wb(&main_wt)
+        .args(["rename", "tw2", "twin"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: cannot write"));
+    assert!(root.join("tw2").join("renamed").is_file());
+}
@@ -0,0 +1,98 @@
+use assert_cmd::assert::OutputAssertExt;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{git, setup_project, wb};
+
+#[test]
+fn foreach_runs_in_every_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt).arg("feature/a").output().unwrap();
+    wb(&main_wt).arg("feature/b").output().unwrap();
+
+    let output = wb(&main_wt)
+        .args(["foreach", "--", "git branch --show-current"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[feature/a] feature/a"), "{}", stdout);
+    assert!(stdout.contains("[feature/b] feature/b"), "{}", stdout);
+    assert!(
+        stdout.contains(&format!("[{0}] {0}", main_branch)),
+        "{}",
+        stdout
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("3 succeeded, 0 failed"));
+}
+
+#[test]
+fn foreach_pattern_and_direct_argv() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/a").output().unwrap();
+
+    wb(&main_wt)
+        .args([
+            "foreach",
+            "--pattern",
+            "feature/*",
+            "--",
+            "printenv",
+            "WB_BRANCH",
+        ])
+        .assert()
+        .success()
+        .stdout("[feature/a] feature/a\n");
+}
+
+#[test]
+fn foreach_reports_failures() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("broken").output().unwrap();
+    std::fs::write(root.join("broken").join("fail"), "").unwrap();
+
+    wb(&main_wt)
+        .args(["foreach", "-k", "--", "test ! -e fail || exit 3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("FAIL  broken (exit 3)"))
+        .stderr(predicate::str::contains("1 succeeded, 1 failed"));
+
+    // Without --keep-going, nothing new starts after the first failure
+    wb(&main_wt)
+        .args(["foreach", "-j", "1", "--", "exit 1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("0 succeeded, 1 failed, 1 skipped"));
+}
+
+#[test]
+fn foreach_forwards_output_past_invalid_utf8() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    // Enough output after the bad line to fill the pipe if nobody reads it
+    let output = wb(&main_wt)
+        .args([
+            "foreach",
+            "--",
+            "printf 'caf\\351\\n'; echo second line; seq 100000",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stdout.contains("] caf\u{fffd}\n"), "{}", stdout);
+    assert!(stdout.contains("] second line\n"), "{}", stdout);
+    assert!(stdout.contains("] 100000\n"));
+    assert!(stderr.contains("1 succeeded, 0 failed"), "{}", stderr);
+}
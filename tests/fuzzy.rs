@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, git, setup_project, wb};
+
+#[test]
+fn create_jumps_to_unique_substring_match() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/auth-refactor").output().unwrap();
+
+    let output = wb(&main_wt).args(["create", "auth"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        cd_target(&output.stdout),
+        Some(root.join("feature--auth-refactor"))
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("'auth' matched 'feature/auth-refactor'"),
+        "{}",
+        stderr
+    );
+    assert!(git(&main_wt, &["branch", "--list", "auth"]).is_empty());
+
+    // A start point always creates the branch as named
+    let output = wb(&main_wt)
+        .args(["create", "auth", "HEAD"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(cd_target(&output.stdout), Some(root.join("auth")));
+}
+
+#[test]
+fn ambiguous_match_lists_suggestions_without_a_tty() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/auth-a").output().unwrap();
+    wb(&main_wt).arg("feature/auth-b").output().unwrap();
+
+    let output = wb(&main_wt).args(["env", "auth"]).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("'auth' matches several branches:\n  feature/auth-a\n  feature/auth-b"),
+        "{}",
+        stderr
+    );
+
+    let output = wb(&main_wt).args(["env", "auth-b"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("WB_BRANCH=feature/auth-b\n"), "{}", stdout);
+}
+
+#[test]
+fn unmatched_names_still_create_or_fail() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/auth").output().unwrap();
+
+    // Loose subsequence matches never stop a new branch from being created
+    let output = wb(&main_wt).args(["create", "fth"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(cd_target(&output.stdout), Some(root.join("fth")));
+
+    let output = wb(&main_wt)
+        .args(["session", "nothing-like-it"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("branch 'nothing-like-it' not found"));
+}
+
+#[test]
+fn land_and_rename_need_the_exact_name_without_a_tty() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/auth-refactor").output().unwrap();
+    let main_sha = git(&main_wt, &["rev-parse", "HEAD"]);
+
+    let output = wb(&main_wt).args(["land", "auth"]).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "no branch is named 'auth'; give the exact name of one of:\n  feature/auth-refactor"
+        ),
+        "{}",
+        stderr
+    );
+    assert_eq!(git(&main_wt, &["rev-parse", "HEAD"]), main_sha);
+    assert!(root.join("feature--auth-refactor").is_dir());
+
+    let output = wb(&main_wt)
+        .args(["rename", "renamed", "auth"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(!git(&main_wt, &["branch", "--list", "feature/auth-refactor"]).is_empty());
+}
+
+#[test]
+fn create_takes_ambiguous_name_as_new_without_a_tty() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    wb(&main_wt).arg("feature/login-page").output().unwrap();
+
+    let output = wb(&main_wt).args(["create", "fea"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(cd_target(&output.stdout), Some(root.join("fea")));
+    assert!(!git(&main_wt, &["branch", "--list", "fea"]).is_empty());
+}
+
+#[test]
+fn create_offers_new_name_next_to_unique_match_on_a_tty() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feature/login-page").output().unwrap();
+    if Command::new("script").arg("--version").output().is_err() {
+        eprintln!("script not installed; skipping");
+        return;
+    }
+
+    // `script` gives wb a terminal; answer the prompt with the second option
+    let command = format!("{} create login", env!("CARGO_BIN_EXE_wb"));
+    let mut child = Command::new("script")
+        .args(["-qec", &command, "/dev/null"])
+        .current_dir(&main_wt)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"2\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(
+        stdout.contains("'login' matches an existing branch:"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("create new branch 'login'"), "{}", stdout);
+    assert!(root.join("login").is_dir());
+    assert!(!git(&main_wt, &["branch", "--list", "login"]).is_empty());
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{git, setup_project, wb};
+
+#[test]
+fn post_create_runs_in_new_worktree_with_env() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(
+        &main_wt,
+        &[
+            "config",
+            "wb.hook.postCreate",
+            r#"echo "$WB_HOOK $WB_BRANCH $WB_WORKTREE" > hook.out"#,
+        ],
+    );
+    git(
+        &main_wt,
+        &["config", "--add", "wb.hook.postCreate", "echo noisy"],
+    );
+
+    let output = wb(&main_wt).arg("feat").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("noisy"), "hook output leaked to stdout");
+    assert!(String::from_utf8(output.stderr).unwrap().contains("noisy"));
+
+    let feat_wt = root.join("feat");
+    assert_eq!(
+        fs::read_to_string(feat_wt.join("hook.out")).unwrap(),
+        format!("postCreate feat {}\n", feat_wt.display())
+    );
+}
+
+#[test]
+fn failing_pre_delete_aborts_delete() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    git(&main_wt, &["config", "wb.hook.preDelete", "exit 3"]);
+
+    let output = wb(&main_wt).args(["-D", "feat"]).output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("preDelete hook 'exit 3' failed with exit code 3"),
+        "{}",
+        stderr
+    );
+    assert!(root.join("feat").is_dir());
+    assert!(!git(&main_wt, &["branch", "--list", "feat"]).is_empty());
+
+    git(&main_wt, &["config", "wb.hook.preDelete", "true"]);
+    let output = wb(&main_wt).args(["-D", "feat"]).output().unwrap();
+    assert!(output.status.success());
+    assert!(!root.join("feat").exists());
+}
+
+#[test]
+fn hook_scripts_in_bare_dir_run_on_rename_and_copy() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let hooks_dir = root.join(".bare").join("wb-hooks");
+    fs::create_dir(&hooks_dir).unwrap();
+    for name in ["post-rename", "post-copy"] {
+        let script = hooks_dir.join(name);
+        fs::write(
+            &script,
+            "#!/bin/sh\necho \"$WB_OLD_BRANCH $WB_BRANCH $WB_OLD_WORKTREE\" > \"$WB_HOOK.out\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    wb(&main_wt).arg("old").output().unwrap();
+
+    let output = wb(&main_wt)
+        .args(["rename", "new", "old"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(root.join("new").join("postRename.out")).unwrap(),
+        format!("old new {}\n", root.join("old").display())
+    );
+
+    let output = wb(&main_wt)
+        .args(["copy", "copied", "new"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(root.join("copied").join("postCopy.out")).unwrap(),
+        format!("new copied {}\n", root.join("new").display())
+    );
+}
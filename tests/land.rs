@@ -0,0 +1,241 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, commit_file, git, setup_clone, setup_project, wb};
+
+#[test]
+fn land_fast_forwards_base_and_removes_branch() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "feature work");
+    let feat_sha = git(&feat_wt, &["rev-parse", "HEAD"]);
+
+    let output = wb(&feat_wt).arg("land").output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(cd_target(&output.stdout), Some(main_wt.clone()));
+    assert_eq!(git(&main_wt, &["rev-parse", "HEAD"]), feat_sha);
+    assert!(main_wt.join("feat.txt").is_file());
+    assert!(!feat_wt.exists(), "feature worktree should be removed");
+    assert!(git(&main_wt, &["branch", "--list", "feat"]).is_empty());
+}
+
+#[test]
+fn land_rebase_strategy_replays_onto_diverged_base() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "feature work");
+    commit_file(&main_wt, "base.txt", "b\n", "base work");
+
+    let output = wb(&feat_wt).arg("land").output().unwrap();
+    assert!(!output.status.success(), "ff-only land should fail");
+    assert!(feat_wt.exists());
+
+    let output = wb(&feat_wt)
+        .args(["land", "--strategy", "rebase"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(git(&main_wt, &["log", "-1", "--format=%s"]), "feature work");
+    assert_eq!(git(&main_wt, &["rev-list", "--merges", "HEAD"]), "");
+    assert!(main_wt.join("base.txt").is_file());
+    assert!(!feat_wt.exists());
+}
+
+#[test]
+fn land_merge_strategy_from_config_creates_merge_commit() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.landStrategy", "merge"]);
+    wb(&main_wt).arg("feat").output().unwrap();
+    commit_file(&root.join("feat"), "feat.txt", "f\n", "feature work");
+
+    let output = wb(&main_wt).args(["land", "feat"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        git(&main_wt, &["rev-list", "--parents", "-1", "HEAD"])
+            .split(' ')
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn land_refuses_dirty_base_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "feature work");
+    fs::write(main_wt.join("file.txt"), "uncommitted\n").unwrap();
+
+    let output = wb(&feat_wt).arg("land").output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("uncommitted changes"), "{}", stderr);
+    assert!(feat_wt.exists());
+    assert!(!main_wt.join("feat.txt").exists());
+}
+
+#[test]
+fn land_refuses_untracked_files_in_branch_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "feature work");
+    fs::write(feat_wt.join("notes.txt"), "scratch\n").unwrap();
+    fs::write(main_wt.join("scratch.txt"), "scratch\n").unwrap();
+    let main_sha = git(&main_wt, &["rev-parse", "HEAD"]);
+
+    let output = wb(&feat_wt).arg("land").output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("worktree for 'feat' has untracked files"),
+        "{}",
+        stderr
+    );
+    assert_eq!(git(&main_wt, &["rev-parse", "HEAD"]), main_sha);
+    assert!(feat_wt.join("notes.txt").is_file());
+}
+
+#[test]
+fn land_aborts_conflicting_merge() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "file.txt", "feature\n", "feature edit");
+    commit_file(&main_wt, "file.txt", "base\n", "base edit");
+    let base_sha = git(&main_wt, &["rev-parse", "HEAD"]);
+
+    let output = wb(&feat_wt)
+        .args(["land", "--strategy", "merge"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(git(&main_wt, &["rev-parse", "HEAD"]), base_sha);
+    assert_eq!(git(&main_wt, &["status", "--porcelain"]), "");
+    assert!(feat_wt.exists());
+}
+
+#[test]
+fn land_stacked_branch_into_its_parent() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt)
+        .args(["create", "a", &main_branch])
+        .output()
+        .unwrap();
+    wb(&main_wt).args(["create", "b", "a"]).output().unwrap();
+    let b_wt = root.join("b");
+    commit_file(&b_wt, "b.txt", "b\n", "b work");
+
+    let output = wb(&b_wt).arg("land").output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(cd_target(&output.stdout), Some(root.join("a")));
+    assert!(root.join("a").join("b.txt").is_file());
+    assert!(!main_wt.join("b.txt").exists());
+}
+
+#[test]
+fn land_into_other_base_with_lagging_upstream() {
+    let tmp = TempDir::new().unwrap();
+    let (_upstream, main_wt) = setup_clone(tmp.path());
+    let root = main_wt.parent().unwrap().to_path_buf();
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt)
+        .args(["create", "release", &main_branch])
+        .output()
+        .unwrap();
+    wb(&main_wt)
+        .args(["create", "feat", &main_branch])
+        .output()
+        .unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "pushed work");
+    git(&feat_wt, &["push", "-u", "origin", "feat"]);
+    commit_file(&feat_wt, "more.txt", "m\n", "local work");
+
+    let output = wb(&feat_wt)
+        .args(["land", "--base", "release"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(cd_target(&output.stdout), Some(root.join("release")));
+    assert!(root.join("release").join("more.txt").is_file());
+    assert!(!feat_wt.exists());
+    assert!(git(&main_wt, &["branch", "--list", "feat"]).is_empty());
+}
+
+#[test]
+fn land_vetoed_by_pre_delete_hook_merges_nothing() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+    let feat_wt = root.join("feat");
+    commit_file(&feat_wt, "feat.txt", "f\n", "feature work");
+    let main_sha = git(&main_wt, &["rev-parse", "HEAD"]);
+    git(&main_wt, &["config", "wb.hook.preDelete", "exit 3"]);
+
+    let output = wb(&feat_wt).arg("land").output().unwrap();
+    assert!(!output.status.success());
+    assert_eq!(git(&main_wt, &["rev-parse", "HEAD"]), main_sha);
+    assert!(feat_wt.is_dir());
+}
+
+#[test]
+fn land_stacked_branch_with_lagging_upstream() {
+    let tmp = TempDir::new().unwrap();
+    let (_upstream, main_wt) = setup_clone(tmp.path());
+    let root = main_wt.parent().unwrap().to_path_buf();
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt)
+        .args(["create", "a", &main_branch])
+        .output()
+        .unwrap();
+    wb(&main_wt).args(["create", "b", "a"]).output().unwrap();
+    let b_wt = root.join("b");
+    commit_file(&b_wt, "b.txt", "b\n", "pushed work");
+    git(&b_wt, &["push", "-u", "origin", "b"]);
+    commit_file(&b_wt, "more.txt", "m\n", "local work");
+
+    let output = wb(&b_wt).arg("land").output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(cd_target(&output.stdout), Some(root.join("a")));
+    assert!(root.join("a").join("more.txt").is_file());
+    assert!(!b_wt.exists());
+    assert!(git(&main_wt, &["branch", "--list", "b"]).is_empty());
+}
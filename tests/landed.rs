@@ -0,0 +1,118 @@
+use assert_cmd::assert::OutputAssertExt;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_project, wb};
+
+#[test]
+fn list_landed_detects_squash_and_rebase_merges() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    for name in ["squashed", "rebased", "unlanded"] {
+        wb(&main_wt).arg(name).output().unwrap();
+        let wt = root.join(name);
+        commit_file(&wt, &format!("{}-1.txt", name), "1\n", "one");
+        commit_file(&wt, &format!("{}-2.txt", name), "2\n", "two");
+    }
+    commit_file(&main_wt, "base.txt", "b\n", "moves the base forward");
+    git(&main_wt, &["merge", "--squash", "squashed"]);
+    git(&main_wt, &["commit", "-m", "squash merge"]);
+    git(
+        &main_wt,
+        &["cherry-pick", &format!("{}..rebased", main_branch)],
+    );
+
+    let output = wb(&main_wt)
+        .args(["list", "--porcelain", "--landed"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let names: Vec<String> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| l.split('\t').next().unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["rebased", "squashed"]);
+
+    // --merged alone does not see them
+    wb(&main_wt)
+        .args(["list", "--merged"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("squashed").not());
+
+    // Safe delete accepts landed branches but still refuses unlanded ones
+    wb(&main_wt)
+        .args(["-d", "squashed", "rebased"])
+        .assert()
+        .success();
+    wb(&main_wt)
+        .args(["-d", "unlanded"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not fully merged"));
+    assert!(!root.join("squashed").exists());
+    assert!(root.join("unlanded").is_dir());
+}
+
+#[test]
+fn clean_offers_squash_merged_branches() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("squashed").output().unwrap();
+    commit_file(&root.join("squashed"), "s.txt", "s\n", "work");
+    git(&main_wt, &["merge", "--squash", "squashed"]);
+    git(&main_wt, &["commit", "-m", "squash merge"]);
+
+    wb(&main_wt)
+        .args(["clean", "--dry-run", "--no-fetch"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("squashed"))
+        .stderr(predicate::str::contains("squash/rebase-merged into"));
+}
+
+#[test]
+fn squash_detection_needs_no_identity_and_reuses_its_probe() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    wb(&main_wt).arg("squashed").output().unwrap();
+    let wt = root.join("squashed");
+    commit_file(&wt, "a.txt", "a\n", "one");
+    commit_file(&wt, "b.txt", "b\n", "two");
+    commit_file(&main_wt, "base.txt", "b\n", "moves the base forward");
+    git(&main_wt, &["merge", "--squash", "squashed"]);
+    git(&main_wt, &["commit", "-m", "squash merge"]);
+
+    git(&main_wt, &["config", "--unset", "user.name"]);
+    git(&main_wt, &["config", "--unset", "user.email"]);
+    git(&main_wt, &["config", "user.useConfigOnly", "true"]);
+    let home = tmp.path().join("home");
+    std::fs::create_dir(&home).unwrap();
+    let list_landed = || {
+        let output = wb(&main_wt)
+            .args(["list", "--porcelain", "--landed"])
+            .env("HOME", &home)
+            .env("XDG_CONFIG_HOME", &home)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env_remove("GIT_AUTHOR_NAME")
+            .env_remove("GIT_AUTHOR_EMAIL")
+            .env_remove("GIT_COMMITTER_NAME")
+            .env_remove("GIT_COMMITTER_EMAIL")
+            .env_remove("EMAIL")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(list_landed().starts_with("squashed\t"));
+    let objects = git(&main_wt, &["count-objects"]);
+    assert!(list_landed().starts_with("squashed\t"));
+    assert_eq!(git(&main_wt, &["count-objects"]), objects);
+}
@@ -0,0 +1,342 @@
+use assert_cmd::assert::OutputAssertExt;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{git, setup_project, wb};
+
+#[test]
+fn list_json_joins_worktrees() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    wb(&main_wt).arg("feature/a").output().unwrap();
+    git(&main_wt, &["branch", "no-worktree"]);
+
+    let output = wb(&main_wt).args(["list", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+
+    let find = |name: &str| {
+        entries
+            .iter()
+            .find(|e| e["name"] == name)
+            .unwrap_or_else(|| panic!("missing {}", name))
+    };
+
+    let main = find(&main_branch);
+    assert_eq!(main["is_current"], true);
+    assert_eq!(main["sha"], git(&main_wt, &["rev-parse", "HEAD"]));
+
+    let feature = find("feature/a");
+    assert_eq!(feature["is_current"], false);
+    let path = feature["worktree"]["path"].as_str().unwrap();
+    assert_eq!(
+        std::path::Path::new(path).canonicalize().unwrap(),
+        root.join("feature--a").canonicalize().unwrap()
+    );
+    assert_eq!(feature["worktree"]["is_detached"], false);
+
+    assert!(find("no-worktree")["worktree"].is_null());
+}
+
+#[test]
+fn list_porcelain_is_tab_separated() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    let sha = git(&main_wt, &["rev-parse", "HEAD"]);
+    git(&main_wt, &["branch", "other"]);
+
+    let output = wb(&main_wt).args(["list", "--porcelain"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<Vec<&str>> = stdout.lines().map(|l| l.split('\t').collect()).collect();
+
+    let main = lines.iter().find(|f| f[0] == main_branch).unwrap();
+    assert_eq!(main[1], sha);
+    assert_eq!(main[2], "*");
+    assert!(!main[3].is_empty());
+
+    let other = lines.iter().find(|f| f[0] == "other").unwrap();
+    assert_eq!(other[2], "-");
+    assert_eq!(other[3], "");
+}
+
+#[test]
+fn list_verbose_shows_tracking_and_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    wb(&main_wt).arg("feat").output().unwrap();
+    git(
+        &main_wt,
+        &["branch", "--set-upstream-to", &main_branch, "feat"],
+    );
+    common::commit_file(&main_wt, "two.txt", "2\n", "second on main");
+
+    git(&main_wt, &["branch", "stale"]);
+    git(&main_wt, &["config", "branch.stale.remote", "."]);
+    git(
+        &main_wt,
+        &["config", "branch.stale.merge", "refs/heads/removed"],
+    );
+
+    let output = wb(&main_wt).args(["list", "-v"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let feat = stdout.lines().find(|l| l.contains("feat")).unwrap();
+    assert!(feat.starts_with("+ feat"), "{}", feat);
+    assert!(feat.contains("[behind 1] init"), "{}", feat);
+    assert!(
+        !feat.contains("feat ("),
+        "-v should not show the worktree path"
+    );
+    let stale = stdout.lines().find(|l| l.contains("stale")).unwrap();
+    assert!(stale.contains("[gone]"), "{}", stale);
+
+    let output = wb(&main_wt).arg("-vv").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let feat = stdout.lines().find(|l| l.contains("feat")).unwrap();
+    let feat_path = root.join("feat").canonicalize().unwrap();
+    assert!(
+        feat.contains(&format!("({})", feat_path.display())),
+        "{}",
+        feat
+    );
+    assert!(
+        feat.contains(&format!("[{}: behind 1]", main_branch)),
+        "{}",
+        feat
+    );
+}
+
+/// Names printed by `wb list --porcelain` with the given extra arguments.
+fn listed(dir: &std::path::Path, args: &[&str]) -> Vec<String> {
+    let output = wb(dir)
+        .args(["list", "--porcelain"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "wb list {:?} failed", args);
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| l.split('\t').next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn list_filters() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    let base = git(&main_wt, &["rev-parse", "HEAD"]);
+
+    git(&main_wt, &["branch", "feature/merged"]);
+    git(&main_wt, &["branch", "feature/open"]);
+    wb(&main_wt).arg("feature/open").output().unwrap();
+    let open_wt = main_wt.parent().unwrap().join("feature--open");
+    common::commit_file(&open_wt, "open.txt", "o\n", "open work");
+
+    assert_eq!(
+        listed(&main_wt, &["--merged"]),
+        vec!["feature/merged".to_string(), main_branch.clone()]
+    );
+    assert_eq!(listed(&main_wt, &["--no-merged"]), vec!["feature/open"]);
+    assert_eq!(
+        listed(&main_wt, &["--contains", "feature/open"]),
+        vec!["feature/open"]
+    );
+    assert_eq!(
+        listed(&main_wt, &["--no-contains", &base]),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        listed(&main_wt, &["feature/*", "--no-merged", &main_branch]),
+        vec!["feature/open"]
+    );
+    assert_eq!(
+        listed(&main_wt, &["--sort=-refname", "feature/*"]),
+        vec!["feature/open", "feature/merged"]
+    );
+}
+
+#[test]
+fn list_mine_filters_by_committer() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("theirs").output().unwrap();
+    let theirs_wt = main_wt.parent().unwrap().join("theirs");
+    git(
+        &theirs_wt,
+        &[
+            "-c",
+            "user.email=other@test.com",
+            "commit",
+            "--allow-empty",
+            "-m",
+            "someone else",
+        ],
+    );
+
+    let mine = listed(&main_wt, &["--mine"]);
+    assert!(!mine.contains(&"theirs".to_string()), "{:?}", mine);
+    assert_eq!(mine.len(), 1);
+}
+
+#[test]
+fn filters_imply_list_mode_on_bare_command() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["branch", "side"]);
+
+    wb(&main_wt)
+        .args(["--merged", "side"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("side"));
+}
+
+#[test]
+fn list_remotes_marks_local_counterparts() {
+    let tmp = TempDir::new().unwrap();
+    let (upstream, main_wt) = common::setup_clone(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    git(&upstream, &["branch", "remote-only"]);
+    git(&main_wt, &["fetch", "origin"]);
+
+    let output = wb(&main_wt).args(["list", "-r"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains(&format!("origin/{} (local: {})", main_branch, main_branch)),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("origin/remote-only (wb create remote-only)"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("origin/HEAD"), "{}", stdout);
+    assert!(
+        !stdout.lines().any(|l| l.trim() == main_branch),
+        "-r should not list local branches"
+    );
+
+    let all = listed(&main_wt, &["-a"]);
+    assert!(all.contains(&main_branch));
+    assert!(all.contains(&"origin/remote-only".to_string()));
+
+    // The remote-only branch can be materialized as a worktree that tracks it
+    wb(&main_wt).arg("remote-only").assert().success();
+    let wt = main_wt.parent().unwrap().join("remote-only");
+    assert_eq!(
+        git(&wt, &["rev-parse", "--abbrev-ref", "@{upstream}"]),
+        "origin/remote-only"
+    );
+}
+
+#[test]
+fn list_worktrees_shows_on_disk_state() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    wb(&main_wt).arg("locked-one").output().unwrap();
+    git(
+        &main_wt,
+        &["worktree", "lock", "--reason", "on usb", "../locked-one"],
+    );
+    git(&main_wt, &["worktree", "add", "--detach", "../detached"]);
+    wb(&main_wt).arg("orphan").output().unwrap();
+    git(&main_wt, &["update-ref", "-d", "refs/heads/orphan"]);
+    wb(&main_wt).arg("vanished").output().unwrap();
+    std::fs::remove_dir_all(root.join("vanished")).unwrap();
+    std::fs::create_dir(root.join("leftover")).unwrap();
+
+    let output = wb(&main_wt).args(["list", "--worktrees"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = |needle: &str| {
+        stdout
+            .lines()
+            .find(|l| l.contains(needle))
+            .unwrap_or_else(|| panic!("no line with {} in:\n{}", needle, stdout))
+            .to_string()
+    };
+
+    assert!(line("/detached").contains("(detached HEAD)"));
+    assert!(line("/locked-one").contains("locked (on usb)"));
+    assert!(line("/orphan").contains("[orphan] (branch deleted)"));
+    assert!(line("/vanished").contains("prunable"));
+    assert!(line("/leftover").contains("(stray: not a registered worktree)"));
+    let main_name = main_wt.file_name().unwrap().to_string_lossy().to_string();
+    assert!(line(&format!("/{} ", main_name)).starts_with("* "));
+
+    let output = wb(&main_wt)
+        .args(["list", "--worktrees", "--json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    assert!(entries
+        .iter()
+        .any(|e| e["is_stray"] == true && e["path"].as_str().unwrap().ends_with("leftover")));
+    assert!(entries
+        .iter()
+        .any(|e| e["is_orphaned"] == true && e["branch"] == "orphan"));
+}
+
+#[test]
+fn list_format_template_and_config_default() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    let short = git(&main_wt, &["rev-parse", "--short", "HEAD"]);
+
+    wb(&main_wt).arg("feat").output().unwrap();
+    std::fs::write(root.join("feat").join("scratch.txt"), "x").unwrap();
+
+    let output = wb(&main_wt)
+        .args([
+            "list",
+            "--format",
+            "{current}{dirty} {branch}|{sha}|{path}|{{x}}",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let feat_path = root.join("feat").canonicalize().unwrap();
+    assert!(
+        stdout.contains(&format!(" * feat|{}|{}|{{x}}", short, feat_path.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!("*  {}|{}|", main_branch, short)),
+        "{}",
+        stdout
+    );
+
+    git(
+        &main_wt,
+        &["config", "wb.listFormat", "{branch}={ahead}/{behind}"],
+    );
+    wb(&main_wt)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("feat=0/0"));
+
+    wb(&main_wt)
+        .args(["list", "--format", "{bogus}"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown placeholder '{bogus}'"));
+}
@@ -0,0 +1,81 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, setup_project, wb};
+
+#[test]
+fn back_toggles_between_the_last_two_worktrees() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let root = root.canonicalize().unwrap();
+    let main_wt = main_wt.canonicalize().unwrap();
+
+    let output = wb(&main_wt).arg("feat").output().unwrap();
+    assert_eq!(cd_target(&output.stdout), Some(root.join("feat")));
+
+    let output = wb(&root.join("feat")).arg("-").output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(cd_target(&output.stdout), Some(main_wt.clone()));
+
+    let output = wb(&main_wt).arg("back").output().unwrap();
+    assert_eq!(cd_target(&output.stdout), Some(root.join("feat")));
+}
+
+#[test]
+fn recent_lists_and_jumps_skipping_removed_worktrees() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let root = root.canonicalize().unwrap();
+    let main_wt = main_wt.canonicalize().unwrap();
+    let default = main_wt.file_name().unwrap().to_string_lossy().to_string();
+    wb(&main_wt).arg("one").output().unwrap();
+    wb(&main_wt).arg("two").output().unwrap();
+    wb(&main_wt).arg("three").output().unwrap();
+    wb(&main_wt)
+        .args(["delete", "--force", "two"])
+        .output()
+        .unwrap();
+
+    let output = wb(&root.join("three")).arg("recent").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let width = default.len().max(3);
+    assert_eq!(
+        stdout,
+        format!(
+            "  1  {:<w$}  {}\n  2  {:<w$}  {}\n",
+            default,
+            main_wt.display(),
+            "one",
+            root.join("one").display(),
+            w = width
+        )
+    );
+
+    let output = wb(&root.join("three"))
+        .args(["recent", "2"])
+        .output()
+        .unwrap();
+    assert_eq!(cd_target(&output.stdout), Some(root.join("one")));
+
+    let output = wb(&root.join("three"))
+        .args(["recent", "9"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let history = fs::read_to_string(root.join(".bare/wb-history")).unwrap();
+    assert!(history.starts_with(&format!("{}\n", root.join("one").display())));
+}
+
+#[test]
+fn back_without_history_fails() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    let output = wb(&main_wt).arg("back").output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no previous worktree"));
+}
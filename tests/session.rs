@@ -0,0 +1,123 @@
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{git, setup_project, wb};
+
+/// Run tmux against the isolated server in `tmux_dir`.
+fn tmux(tmux_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("tmux")
+        .args(args)
+        .env("TMUX_TMPDIR", tmux_dir)
+        .env_remove("TMUX")
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[test]
+fn create_spawns_and_delete_kills_tmux_session() {
+    let tmp = TempDir::new().unwrap();
+    let tmux_dir = TempDir::new().unwrap();
+    if tmux(tmux_dir.path(), &["-V"]).is_none() {
+        eprintln!("tmux not installed; skipping");
+        return;
+    }
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.multiplexer", "tmux"]);
+    git(&main_wt, &["config", "wb.sessionOnCreate", "true"]);
+    git(&main_wt, &["config", "wb.sessionOnDelete", "true"]);
+    git(&main_wt, &["config", "wb.sessionWindows", "editor"]);
+    git(
+        &main_wt,
+        &[
+            "config",
+            "--add",
+            "wb.sessionWindows",
+            "server=echo serving",
+        ],
+    );
+
+    let output = wb(&main_wt)
+        .arg("feature/v1.2")
+        .env("TMUX_TMPDIR", tmux_dir.path())
+        .env_remove("TMUX")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let session = "=feature/v1_2";
+    assert!(tmux(tmux_dir.path(), &["has-session", "-t", session]).is_some());
+    let windows = tmux(
+        tmux_dir.path(),
+        &[
+            "list-windows",
+            "-t",
+            session,
+            "-F",
+            "#W #{pane_current_path}",
+        ],
+    )
+    .unwrap();
+    let wt_path = root.join("feature--v1.2");
+    assert_eq!(
+        windows,
+        format!("editor {}\nserver {}", wt_path.display(), wt_path.display())
+    );
+
+    let output = wb(&main_wt)
+        .args(["-D", "feature/v1.2"])
+        .env("TMUX_TMPDIR", tmux_dir.path())
+        .env_remove("TMUX")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(tmux(tmux_dir.path(), &["has-session", "-t", session]).is_none());
+
+    tmux(tmux_dir.path(), &["kill-server"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn failed_zellij_spawn_is_reported() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+    let bin = tmp.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let zellij = bin.join("zellij");
+    std::fs::write(
+        &zellij,
+        "#!/bin/sh\n[ \"$1\" = list-sessions ] && exit 0\necho 'no server for you' >&2\nexit 1\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&zellij, std::fs::Permissions::from_mode(0o755)).unwrap();
+    git(&main_wt, &["config", "wb.multiplexer", "zellij"]);
+    git(&main_wt, &["config", "wb.sessionOnCreate", "true"]);
+
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = wb(&main_wt)
+        .arg("feat")
+        .env("PATH", path)
+        .env_remove("ZELLIJ")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("could not start session 'feat': no server for you"),
+        "{}",
+        stderr
+    );
+}
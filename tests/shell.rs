@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, git, setup_project, wb};
+
+#[test]
+fn cd_directive_goes_to_the_directive_file() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let directives = tmp.path().join("directives");
+    fs::write(&directives, "").unwrap();
+    // A nested wb run by a hook must not steer the outer shell
+    git(
+        &main_wt,
+        &["config", "wb.hook.postCreate", "env > hook-env"],
+    );
+
+    let output = wb(&main_wt)
+        .arg("feat")
+        .env("WB_DIRECTIVE_FILE", &directives)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(cd_target(&output.stdout), None);
+    assert_eq!(
+        cd_target(fs::read_to_string(&directives).unwrap().as_bytes()),
+        Some(root.join("feat"))
+    );
+
+    let hook_env = fs::read_to_string(root.join("feat/hook-env")).unwrap();
+    assert!(!hook_env.contains("WB_DIRECTIVE_FILE"), "{}", hook_env);
+}
+
+#[test]
+fn cd_directive_falls_back_to_stdout() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    let output = wb(&main_wt)
+        .arg("feat")
+        .env_remove("WB_DIRECTIVE_FILE")
+        .output()
+        .unwrap();
+    assert_eq!(cd_target(&output.stdout), Some(root.join("feat")));
+}
+
+#[test]
+fn bash_wrapper_cds_and_keeps_output() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let wb_bin = Path::new(env!("CARGO_BIN_EXE_wb"));
+    let bin_dir = wb_bin.parent().unwrap();
+
+    let script = r#"eval "$(command wb init bash)"
+wb feat
+echo "pwd:$PWD"
+wb list"#;
+    let output = Command::new("bash")
+        .args(["-c", script])
+        .current_dir(&main_wt)
+        .env(
+            "PATH",
+            format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap()),
+        )
+        .env("TMPDIR", tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let feat = root.join("feat").canonicalize().unwrap();
+    assert!(
+        stdout.contains(&format!("pwd:{}\n", feat.display())),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("* feat\n"), "{}", stdout);
+    assert!(!stdout.contains("__wb_cd:"), "{}", stdout);
+    // The wrapper cleans up its directive file
+    assert!(!fs::read_dir(tmp.path()).unwrap().any(|e| e
+        .unwrap()
+        .file_name()
+        .to_string_lossy()
+        .starts_with("wb.")));
+}
+
+#[test]
+fn bash_wrapper_under_a_custom_name() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let bin_dir = Path::new(env!("CARGO_BIN_EXE_wb")).parent().unwrap();
+
+    let script = r#"eval "$(command wb init bash --cmd w)"
+w feat
+echo "pwd:$PWD"
+complete -p w"#;
+    let output = Command::new("bash")
+        .args(["-c", script])
+        .current_dir(&main_wt)
+        .env(
+            "PATH",
+            format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap()),
+        )
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let feat = root.join("feat").canonicalize().unwrap();
+    assert!(
+        stdout.contains(&format!("pwd:{}\n", feat.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("complete -F _wb_completions w\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn init_rejects_bad_wrapper_options() {
+    let tmp = TempDir::new().unwrap();
+
+    let output = wb(tmp.path())
+        .args(["init", "zsh", "--bind", "f5"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unsupported key 'f5'"));
+
+    let output = wb(tmp.path())
+        .args(["init", "--cmd", "w"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("only apply to shell integration"));
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_project, wb};
+
+/// Project with `a` stacked on the default branch and `b` stacked on `a`,
+/// each with one commit. Returns the project root and the default worktree.
+fn setup_stack(root: &std::path::Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let (root, main_wt) = setup_project(root);
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt)
+        .args(["create", "a", &main_branch])
+        .output()
+        .unwrap();
+    commit_file(&root.join("a"), "a.txt", "a\n", "a work");
+    wb(&main_wt).args(["create", "b", "a"]).output().unwrap();
+    commit_file(&root.join("b"), "b.txt", "b\n", "b work");
+    (root, main_wt)
+}
+
+#[test]
+fn create_records_parent_and_list_shows_tree() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_stack(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    assert_eq!(git(&main_wt, &["config", "branch.a.wbParent"]), main_branch);
+    assert_eq!(git(&main_wt, &["config", "branch.b.wbParent"]), "a");
+
+    let output = wb(&main_wt).args(["list", "--stack"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        format!("* {}\n  └─ a +\n     └─ b +\n", main_branch)
+    );
+}
+
+#[test]
+fn restack_rebases_children_onto_rewritten_parent() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_stack(tmp.path());
+    let a_wt = root.join("a");
+
+    // Rewrite a's commit; a plain rebase of b would conflict on a.txt
+    fs::write(a_wt.join("a.txt"), "a amended\n").unwrap();
+    git(&a_wt, &["commit", "-a", "--amend", "-m", "a work, amended"]);
+
+    let output = wb(&main_wt).arg("restack").output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("restacked b onto a"), "{}", stderr);
+
+    let b_wt = root.join("b");
+    assert_eq!(
+        git(&b_wt, &["rev-parse", "HEAD~1"]),
+        git(&a_wt, &["rev-parse", "HEAD"])
+    );
+    assert_eq!(git(&b_wt, &["log", "-1", "--format=%s"]), "b work");
+    assert_eq!(
+        fs::read_to_string(b_wt.join("a.txt")).unwrap(),
+        "a amended\n"
+    );
+}
+
+#[test]
+fn restack_stops_on_conflict() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_stack(tmp.path());
+    commit_file(&root.join("b"), "file.txt", "from b\n", "b edit");
+    commit_file(&root.join("a"), "file.txt", "from a\n", "a edit");
+
+    let output = wb(&main_wt).arg("restack").output().unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("conflict while rebasing 'b' onto 'a'"),
+        "{}",
+        stderr
+    );
+    assert!(root.join(".bare/worktrees/b/rebase-merge").is_dir());
+}
+
+#[test]
+fn delete_and_rename_keep_children_attached() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_stack(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    let output = wb(&main_wt).args(["rename", "a2", "a"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(git(&main_wt, &["config", "branch.b.wbParent"]), "a2");
+
+    let output = wb(&main_wt).args(["-D", "a2"]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(git(&main_wt, &["config", "branch.b.wbParent"]), main_branch);
+}
+
+#[test]
+fn safe_delete_accepts_branch_merged_into_its_parent() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_stack(tmp.path());
+    git(&root.join("a"), &["merge", "--squash", "b"]);
+    git(&root.join("a"), &["commit", "-m", "squash b"]);
+
+    let output = wb(&main_wt).args(["-d", "b"]).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!root.join("b").exists());
+}
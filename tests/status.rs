@@ -0,0 +1,67 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_project, wb};
+
+#[test]
+fn status_reports_every_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+
+    // Staged, modified and untracked changes plus a stash
+    wb(&main_wt).arg("work").output().unwrap();
+    let work = root.join("work");
+    fs::write(work.join("file.txt"), "stashed\n").unwrap();
+    git(&work, &["stash"]);
+    fs::write(work.join("staged.txt"), "s\n").unwrap();
+    git(&work, &["add", "staged.txt"]);
+    fs::write(work.join("file.txt"), "changed\n").unwrap();
+    fs::write(work.join("new.txt"), "n\n").unwrap();
+
+    // A merge left in progress by a conflict
+    wb(&main_wt).arg("conflict").output().unwrap();
+    let conflict = root.join("conflict");
+    commit_file(&conflict, "file.txt", "ours\n", "ours");
+    commit_file(&main_wt, "file.txt", "theirs\n", "theirs");
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    let merge = std::process::Command::new("git")
+        .args(["merge", &main_branch])
+        .current_dir(&conflict)
+        .output()
+        .unwrap();
+    assert!(!merge.status.success());
+
+    let output = wb(&main_wt).arg("status").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = |needle: &str| {
+        stdout
+            .lines()
+            .find(|l| l.contains(needle))
+            .unwrap_or_else(|| panic!("no line with {} in:\n{}", needle, stdout))
+            .to_string()
+    };
+
+    let work_line = line("work ");
+    assert!(
+        work_line.contains("1 staged, 1 modified, 1 untracked"),
+        "{}",
+        work_line
+    );
+    assert!(work_line.contains("1 stash"), "{}", work_line);
+
+    let conflict_line = line("conflict ");
+    assert!(
+        conflict_line.contains("merge in progress"),
+        "{}",
+        conflict_line
+    );
+    assert!(conflict_line.contains("1 conflicted"), "{}", conflict_line);
+
+    let main_line = line(&format!("{} ", main_branch));
+    assert!(main_line.starts_with("* "), "{}", main_line);
+    assert!(main_line.contains("clean"), "{}", main_line);
+}
@@ -0,0 +1,69 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_clone, wb};
+
+#[test]
+fn sync_fast_forwards_clean_and_worktreeless_branches() {
+    let tmp = TempDir::new().unwrap();
+    let (upstream, main_wt) = setup_clone(tmp.path());
+    let project = main_wt.parent().unwrap().to_path_buf();
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+
+    // Branches to be updated: one without a worktree, one dirty, one diverged
+    for name in ["plain", "dirty", "diverged"] {
+        git(&upstream, &["branch", name]);
+    }
+    git(&main_wt, &["fetch", "origin"]);
+    for name in ["plain", "dirty", "diverged"] {
+        git(
+            &main_wt,
+            &["branch", name, "--track", &format!("origin/{}", name)],
+        );
+    }
+    wb(&main_wt).arg("dirty").output().unwrap();
+    wb(&main_wt).arg("diverged").output().unwrap();
+    fs::write(project.join("dirty").join("file.txt"), "local edit\n").unwrap();
+    commit_file(&project.join("diverged"), "local.txt", "l\n", "local");
+
+    for name in [main_branch.as_str(), "plain", "dirty", "diverged"] {
+        git(&upstream, &["checkout", "-q", name]);
+        commit_file(&upstream, "up.txt", name, "upstream change");
+    }
+
+    let output = wb(&main_wt).arg("sync").output().unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let upstream_sha = |name: &str| git(&upstream, &["rev-parse", name]);
+    assert_eq!(
+        git(&main_wt, &["rev-parse", "HEAD"]),
+        upstream_sha(&main_branch)
+    );
+    assert!(
+        main_wt.join("up.txt").is_file(),
+        "worktree should be updated"
+    );
+    assert_eq!(
+        git(&main_wt, &["rev-parse", "plain"]),
+        upstream_sha("plain")
+    );
+
+    assert!(
+        stderr.contains("skipped dirty (worktree has local changes)"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("skipped diverged (diverged from origin/diverged: ahead 1, behind 1)"),
+        "{}",
+        stderr
+    );
+    assert_ne!(
+        git(&main_wt, &["rev-parse", "dirty"]),
+        upstream_sha("dirty")
+    );
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+mod common;
+
+use common::{commit_file, git, setup_project, wb};
+
+/// Project whose default worktree has ignored build output in `target/` and `node_modules/`.
+fn setup_with_artifacts(root: &std::path::Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let (root, main_wt) = setup_project(root);
+    commit_file(
+        &main_wt,
+        ".gitignore",
+        "target/\nnode_modules/\n",
+        "ignore build output",
+    );
+    fs::create_dir_all(main_wt.join("target/debug")).unwrap();
+    fs::write(main_wt.join("target/debug/app"), "binary\n").unwrap();
+    fs::create_dir_all(main_wt.join("node_modules/pkg")).unwrap();
+    fs::write(main_wt.join("node_modules/pkg/index.js"), "js\n").unwrap();
+    (root, main_wt)
+}
+
+#[test]
+fn create_warm_clones_build_directories() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_artifacts(tmp.path());
+
+    let output = wb(&main_wt)
+        .args(["create", "feat", "--warm"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Warmed node_modules, target from"),
+        "{}",
+        stderr
+    );
+
+    let feat_wt = root.join("feat");
+    assert_eq!(
+        fs::read_to_string(feat_wt.join("target/debug/app")).unwrap(),
+        "binary\n"
+    );
+    assert_eq!(
+        fs::read_to_string(feat_wt.join("node_modules/pkg/index.js")).unwrap(),
+        "js\n"
+    );
+    assert_eq!(git(&feat_wt, &["status", "--porcelain"]), "");
+}
+
+#[test]
+fn create_without_warm_leaves_build_directories_alone() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_artifacts(tmp.path());
+
+    wb(&main_wt).args(["create", "feat"]).output().unwrap();
+    assert!(!root.join("feat/target").exists());
+}
+
+#[test]
+fn create_warm_from_branch_without_worktree_fails_early() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_artifacts(tmp.path());
+    git(&main_wt, &["branch", "other"]);
+
+    let output = wb(&main_wt)
+        .args(["create", "feat", "--warm=other"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(!root.join("feat").exists());
+    assert!(git(&main_wt, &["branch", "--list", "feat"]).is_empty());
+}
+
+#[test]
+fn create_warm_refuses_existing_worktree() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_with_artifacts(tmp.path());
+    wb(&main_wt).arg("feat").output().unwrap();
+
+    let output = wb(&main_wt)
+        .args(["create", "feat", "--warm"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("--warm only applies to new worktrees")
+    );
+    assert!(!root.join("feat/target").exists());
+}
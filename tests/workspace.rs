@@ -0,0 +1,142 @@
+use std::fs;
+
+use serde_json::Value;
+use tempfile::TempDir;
+
+mod common;
+
+use common::{cd_target, git, setup_project, wb};
+
+fn folders(file: &std::path::Path) -> Vec<(String, String)> {
+    let workspace: Value = serde_json::from_str(&fs::read_to_string(file).unwrap()).unwrap();
+    workspace["folders"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f["name"].as_str().unwrap().to_string(),
+                f["path"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn workspace_lists_worktrees_and_follows_changes() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let main_branch = git(&main_wt, &["branch", "--show-current"]);
+    wb(&main_wt).arg("feat").output().unwrap();
+
+    let output = wb(&main_wt).arg("workspace").output().unwrap();
+    assert!(output.status.success());
+    let project = root.file_name().unwrap().to_string_lossy().to_string();
+    let file = root.join(format!("{}.code-workspace", project));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        file.display().to_string()
+    );
+    assert_eq!(
+        folders(&file),
+        vec![
+            ("feat".to_string(), "feat".to_string()),
+            (main_branch.clone(), main_branch.clone()),
+        ]
+    );
+
+    // Settings survive regeneration
+    let mut workspace: Value = serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+    workspace["settings"]["editor.tabSize"] = 2.into();
+    fs::write(&file, workspace.to_string()).unwrap();
+
+    wb(&main_wt)
+        .args(["rename", "renamed", "feat"])
+        .output()
+        .unwrap();
+    wb(&main_wt).arg("other").output().unwrap();
+    wb(&main_wt).args(["-D", "other"]).output().unwrap();
+    assert_eq!(
+        folders(&file),
+        vec![
+            (main_branch.clone(), main_branch),
+            ("renamed".to_string(), "renamed".to_string()),
+        ]
+    );
+    let workspace: Value = serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(workspace["settings"]["editor.tabSize"], 2);
+}
+
+#[test]
+fn open_runs_editor_on_worktree_creating_it_if_needed() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    let log = tmp.path().join("editor.log");
+    git(
+        &main_wt,
+        &["config", "wb.editor", &format!("echo >> {}", log.display())],
+    );
+    git(&main_wt, &["branch", "feat"]);
+
+    let output = wb(&main_wt)
+        .args(["open", "feat"])
+        .env("VISUAL", "false")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(root.join("feat").is_dir());
+    assert_eq!(cd_target(&output.stdout), None, "open should not cd");
+    assert_eq!(
+        fs::read_to_string(&log).unwrap(),
+        format!("{}\n", root.join("feat").display())
+    );
+}
+
+#[test]
+fn open_without_editor_fails() {
+    let tmp = TempDir::new().unwrap();
+    let (_root, main_wt) = setup_project(tmp.path());
+
+    let output = wb(&main_wt)
+        .arg("open")
+        .env_remove("VISUAL")
+        .env_remove("EDITOR")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("no editor configured"));
+}
+
+#[test]
+fn open_creates_nothing_for_missing_branch_or_editor() {
+    let tmp = TempDir::new().unwrap();
+    let (root, main_wt) = setup_project(tmp.path());
+    git(&main_wt, &["config", "wb.editor", "true"]);
+
+    let output = wb(&main_wt)
+        .args(["open", "nosuchbranch"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+    assert!(git(&main_wt, &["branch", "--list", "nosuchbranch"]).is_empty());
+    assert!(!root.join("nosuchbranch").exists());
+
+    git(&main_wt, &["config", "--unset", "wb.editor"]);
+    git(&main_wt, &["branch", "feat"]);
+    let output = wb(&main_wt)
+        .args(["open", "feat"])
+        .env_remove("VISUAL")
+        .env_remove("EDITOR")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no editor configured"));
+    assert!(!root.join("feat").exists());
+}